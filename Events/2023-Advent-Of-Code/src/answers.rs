@@ -0,0 +1,188 @@
+use crate::libaoc::{Part, SolveError, Solution};
+use crate::libaoc::input::read_day_input;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Example,
+    Input
+}
+
+impl InputKind {
+    /// The file a run reads for this kind of input. Both `--verify` and a
+    /// normal run go through here, so they always agree.
+    pub fn path(&self, solution: &dyn Solution, part: Part) -> String {
+        match self {
+            InputKind::Example => solution.example_file(part),
+            InputKind::Input => solution.input_file(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Expected {
+    pub day: u32,
+    pub part: Part,
+    pub input: InputKind,
+    pub answer: &'static str
+}
+
+const fn expect(day: u32, part: Part, input: InputKind, answer: &'static str) -> Expected {
+    Expected { day, part, input, answer }
+}
+
+use InputKind::{Example, Input};
+use Part::{One, Two};
+
+/// Known-good answers. Add a line here once a part has been accepted so
+/// that `--verify` catches any later regression.
+pub const ANSWERS: &[Expected] = &[
+    expect(1, One, Example, "142"),
+    expect(1, Two, Example, "281"),
+    expect(1, One, Input, "55447"),
+    expect(1, Two, Input, "54706"),
+
+    expect(2, One, Example, "8"),
+    expect(2, Two, Example, "2286"),
+    expect(2, One, Input, "2278"),
+    expect(2, Two, Input, "67953"),
+
+    expect(3, One, Example, "4361"),
+    expect(3, Two, Example, "467835"),
+    expect(3, One, Input, "521515"),
+    expect(3, Two, Input, "69527306"),
+
+    expect(4, One, Example, "13"),
+    expect(4, Two, Example, "30"),
+    expect(4, One, Input, "25010"),
+    expect(4, Two, Input, "9924412"),
+
+    expect(5, One, Example, "35"),
+    expect(5, Two, Example, "46"),
+    expect(5, One, Input, "379811651"),
+    expect(5, Two, Input, "27992443"),
+
+    expect(6, One, Example, "288"),
+    expect(6, Two, Example, "71503"),
+    expect(6, One, Input, "114400"),
+    expect(6, Two, Input, "21039729"),
+
+    expect(7, One, Example, "6440"),
+    expect(7, Two, Example, "5905"),
+    expect(7, One, Input, "251058093"),
+    expect(7, Two, Input, "249781879"),
+
+    expect(8, One, Example, "2"),
+    expect(8, Two, Example, "6"),
+    expect(8, One, Input, "11567"),
+    expect(8, Two, Input, "9858474970153"),
+
+    expect(9, One, Example, "114"),
+    expect(9, Two, Example, "2"),
+    expect(9, One, Input, "1987402313"),
+    expect(9, Two, Input, "900"),
+
+    expect(10, One, Example, "4"),
+    expect(10, Two, Example, "4"),
+    expect(10, One, Input, "7005"),
+    expect(10, Two, Input, "417"),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail(String),
+    /// The solution gave up on the input instead of answering.
+    Error(String),
+    Unsolved,
+    Unreadable(String)
+}
+
+#[derive(Debug)]
+pub struct Check<'a> {
+    pub expected: &'a Expected,
+    pub outcome: Outcome
+}
+
+impl Check<'_> {
+    pub fn passed(&self) -> bool {
+        self.outcome == Outcome::Pass
+    }
+}
+
+/// Runs `solution` on the input named by `expected` and compares the result.
+pub fn check<'a>(solution: &dyn Solution, expected: &'a Expected) -> Check<'a> {
+    let path = expected.input.path(solution, expected.part);
+    let outcome = match read_day_input(expected.day, &path) {
+        Err(e) => Outcome::Unreadable(e.to_string()),
+        Ok(input) => match solution.solve(expected.part, &input) {
+            Err(SolveError::NotImplemented) => Outcome::Unsolved,
+            Err(SolveError::Failed(e)) => Outcome::Error(e),
+            Ok(actual) if actual == expected.answer => Outcome::Pass,
+            Ok(actual) => Outcome::Fail(actual),
+        }
+    };
+    Check { expected, outcome }
+}
+
+/// Formats checks as an aligned table, one row per check.
+pub fn table(checks: &[Check]) -> String {
+    let mut out = format!("{:<4} {:<5} {:<8} {:<20} {:<20} {}\n", "Day", "Part", "Input", "Expected", "Actual", "Status");
+    for c in checks {
+        let input = match c.expected.input {
+            Example => "example",
+            Input => "input",
+        };
+        let (actual, status) = match &c.outcome {
+            Outcome::Pass => (c.expected.answer.to_string(), "ok".to_string()),
+            Outcome::Fail(actual) => (actual.clone(), "FAIL".to_string()),
+            Outcome::Error(e) => ("-".to_string(), format!("ERROR {}", e)),
+            Outcome::Unsolved => ("-".to_string(), "UNSOLVED".to_string()),
+            Outcome::Unreadable(e) => ("-".to_string(), format!("UNREADABLE {}", e)),
+        };
+        out += &format!("{:<4} {:<5} {:<8} {:<20} {:<20} {}\n",
+            format!("{:02}", c.expected.day), c.expected.part.to_string(), input, c.expected.answer, actual, status);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::libaoc::Answer;
+
+    #[test]
+    fn examples_match_expected_answers() {
+        for solution in days::all() {
+            for expected in ANSWERS.iter().filter(|e| e.day == solution.day() && e.input == Example) {
+                let c = check(solution.as_ref(), expected);
+                assert!(c.passed(), "{}", table(&[c]));
+            }
+        }
+    }
+
+    struct Gives(Answer);
+
+    impl Solution for Gives {
+        fn day(&self) -> u32 { 1 }
+        fn part_one(&self, _input: &str) -> Answer { self.0.clone() }
+        fn part_two(&self, _input: &str) -> Answer { self.0.clone() }
+    }
+
+    #[test]
+    fn failures_are_told_apart_from_unsolved_parts() {
+        let expected = &ANSWERS[0];
+        assert_eq!(check(&Gives(Err(SolveError::NotImplemented)), expected).outcome, Outcome::Unsolved);
+        let failed = check(&Gives(Err(SolveError::Failed(String::from("bad input")))), expected);
+        assert_eq!(failed.outcome, Outcome::Error(String::from("bad input")));
+        assert!(!failed.passed());
+        assert!(table(&[failed]).contains("ERROR bad input"));
+    }
+
+    #[test]
+    fn every_answer_belongs_to_a_registered_day() {
+        for expected in ANSWERS {
+            assert!(days::get(expected.day).is_some(), "day {} is not registered", expected.day);
+        }
+    }
+}
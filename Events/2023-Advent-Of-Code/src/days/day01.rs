@@ -1,25 +1,15 @@
-
 use regex::Regex;
 use aho_corasick::AhoCorasick;
-use advent_of_code_2023::libaoc::read_lines;
-
+use crate::libaoc::{Answer, Part, Solution};
 
-// #[cfg(test)]
-// mod tests {
-//     #[test]
-//     fn it_works() {
-//         let result = 2 + 2;
-//         assert_eq!(result, 4);
-//     }
-// }
+pub struct Day01;
 
-
-fn line_to_digits(line:&String) -> Vec<u32>{
+fn line_to_digits(line: &str) -> Vec<u32>{
     let ds: Vec<u32> = line.chars()
         .filter(|c| c.is_ascii_digit() )
         .map(|c| c.to_digit(10).unwrap())
         .collect();
-    return ds
+    ds
 }
 
 fn parse_value(s: &str) -> u32 {
@@ -39,7 +29,7 @@ fn parse_value(s: &str) -> u32 {
 }
 
 #[allow(dead_code)]
-fn line_to_digits_including_words_regex(line: &String) -> Vec<u32> {
+fn line_to_digits_including_words_regex(line: &str) -> Vec<u32> {
     let patterns= "[0-9]|one|two|three|four|five|six|seven|eight|nine";
     let re = Regex::new(patterns).unwrap();
     let tokens = re.captures_iter(line);
@@ -48,10 +38,10 @@ fn line_to_digits_including_words_regex(line: &String) -> Vec<u32> {
         .map(|(s, _)|s)
         .map(parse_value)
         .collect();
-    return digits;    
+    digits
 }
 
-fn line_to_digits_including_words_aho_corasick(line: &String) -> Vec<u32> {
+fn line_to_digits_including_words_aho_corasick(line: &str) -> Vec<u32> {
     let patterns= [
         "1","2","3","4","5","6","7","8","9",
         "one","two","three","four","five","six","seven","eight","nine"];
@@ -61,7 +51,7 @@ fn line_to_digits_including_words_aho_corasick(line: &String) -> Vec<u32> {
         .map(|m| &line[m.start()..m.end()])
         .map(parse_value)
         .collect();
-    return digits;    
+    digits
 }
 
 
@@ -75,43 +65,33 @@ fn concatenate_of_first_and_last_digits(ds: Vec<u32>) -> u32 {
         }
         return v;
     }
-    return 0;
-    
-}
+    0
 
-#[allow(dead_code)]
-fn debug_output(f: fn(&String)->Vec<u32>) -> impl Fn(&String) -> Vec<u32>{
-    let inf = move |s:&String| {
-        println!("input: {}", s);
-        
-        let r =  f(s);
-        println!("digits: {}", r.iter().map(|x| x.to_string()).collect::<String>() );
-        return r;
-    };
-    return inf;
 }
 
-#[allow(dead_code)]
-fn printer(t: u32) -> u32 {
-    println!("{}", t);
-    return t;
-}
+impl Solution for Day01 {
+    fn day(&self) -> u32 { 1 }
 
-fn main() {
-    println!("Advent of code day 01");
-    let lines = read_lines("files/01-input.txt");
-    
-    let total_simple = lines.iter()
-        .map(line_to_digits)
-        .map(concatenate_of_first_and_last_digits)
-        .reduce(|acc, e| acc+e).unwrap();
-    println!("digits only: {}", total_simple);
+    fn example_file(&self, part: Part) -> String {
+        match part {
+            Part::One => String::from("files/01-example.txt"),
+            Part::Two => String::from("files/01-example-2.txt"),
+        }
+    }
 
-    let total_complex: u32 = lines.iter()
-        .map(line_to_digits_including_words_aho_corasick)
+    fn part_one(&self, input: &str) -> Answer {
+        let total_simple: u32 = input.lines()
+            .map(line_to_digits)
             .map(concatenate_of_first_and_last_digits)
-            // .map(printer)
-            .reduce(|acc, e| acc+e).unwrap();
-    println!("digits and words: {}", total_complex);
+            .sum();
+        Ok(total_simple.to_string())
+    }
 
-}
\ No newline at end of file
+    fn part_two(&self, input: &str) -> Answer {
+        let total_complex: u32 = input.lines()
+            .map(line_to_digits_including_words_aho_corasick)
+            .map(concatenate_of_first_and_last_digits)
+            .sum();
+        Ok(total_complex.to_string())
+    }
+}
@@ -0,0 +1,146 @@
+// game is made of rounds,
+// rounds are made of turns,
+// turns are made of colours - number pairs.
+
+use std::collections::HashMap;
+use std::cmp;
+
+use crate::libaoc::{Answer, Part, Solution};
+use crate::libaoc::parse::{lines, parse_all, separated, Cursor, ParseError};
+
+pub struct Day02;
+
+#[derive(Debug)]
+struct Game{
+    index: u32,
+    turns: Vec<Turn>
+}
+
+
+type Turn = HashMap<String,u32>;
+type Constraint = HashMap<String,u32>;
+
+fn parse_turn(c: &mut Cursor) -> Result<Turn, ParseError> {
+    // 1 blue, 2 green
+    let cubes = separated(c, ",", |c| {
+        // 1 blue
+        c.spaces();
+        let n: u32 = c.number()?;
+        c.gap()?;
+        Ok((c.word()?, n))
+    })?;
+    Ok(HashMap::from_iter(cubes))
+}
+
+fn line_to_game(c: &mut Cursor) -> Result<Game, ParseError> {
+    // Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+    c.literal("Game")?;
+    c.gap()?;
+    let index = c.number()?;
+    c.literal(":")?;
+    Ok(Game {
+        index,
+        turns: separated(c, ";", parse_turn)?
+    })
+}
+
+
+fn merge_with_operator<K:std::cmp::Eq+std::hash::Hash+std::clone::Clone,V:std::marker::Copy>(op: impl Fn(V,V)->V, m1: &HashMap<K,V>, m2: &HashMap<K,V> ) -> HashMap<K,V>{
+    let mut m: HashMap<K, V> = HashMap::new();
+    for key in m1.keys().chain(m2.keys()){
+        if let (Some(v1), Some(v2)) = (m1.get(key), m2.get(key)) {
+            m.insert(key.clone(), op(*v1,*v2));
+        }else if let Some(v1) = m1.get(key){
+            m.insert(key.clone(), *v1);
+        }else if let Some(v2) = m2.get(key) {
+            m.insert(key.clone(), *v2);
+        }
+    }
+    m
+
+}
+
+fn max_required(turns: &[Turn]) -> Constraint {
+    let con: Constraint = turns.iter()
+        .fold(HashMap::from([]), |acc, t| merge_with_operator(cmp::max, &acc, t));
+    con
+}
+
+fn turn_possible_given_constraint(constraint: &Constraint, turn: &Turn) -> bool{
+    let possible: bool = turn.iter()
+        .all(|(color,num)| constraint.get(color) >= Some(num));
+    // println!("{:?} is possible? {}", turn, possible);
+    possible
+}
+
+fn game_possible_given_constraint(constraint: &HashMap<String, u32>, game: &Game) -> bool {
+    //for every turn in a game, is the value higher than the constriant?
+    // println!("GAME {:?}", game);
+    // println!("CONSTRAINT {:?}", constraint);
+    let valid_games:bool= game.turns.iter()
+        .all(|t| turn_possible_given_constraint(constraint, t));
+    // println!("GAME VALID? {}\n", valid_games);
+
+    valid_games
+}
+
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_all(input, |c| lines(c, line_to_game))
+}
+
+impl Solution for Day02 {
+    fn day(&self) -> u32 { 2 }
+
+    fn example_file(&self, _part: Part) -> String {
+        String::from("files/02-example-1.txt")
+    }
+
+    fn part_one(&self, input: &str) -> Answer {
+        let constraints: HashMap<String, u32, _> = HashMap::from([
+            ("red".to_string(), 12),
+            ("green".to_string(), 13),
+            ("blue".to_string(), 14)
+        ]);
+
+        let games = parse_games(input)?;
+        let sum_indices: u32 = games.iter()
+            .filter(|g| game_possible_given_constraint(&constraints, g))
+            .map(|g| g.index)
+            .sum();
+        Ok(sum_indices.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        let mut tot = 0;
+        for game in parse_games(input)?.iter() {
+            let constraint = max_required(&game.turns);
+            // println!("GAME {:?}", game);
+            // println!("REQUIRES {:?}\n", constraint);
+            if let (Some(red), Some(green), Some(blue)) = (constraint.get("red"), constraint.get("green"), constraint.get("blue")){
+                tot += red*green*blue;
+            };
+        }
+        Ok(tot.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_games() {
+        let games = parse_all("Game 7: 3 blue, 4 red; 2 green\nGame 8: 1 red\n", |c| lines(c, line_to_game)).unwrap();
+        assert_eq!(games.iter().map(|g| g.index).collect::<Vec<_>>(), vec![7, 8]);
+        assert_eq!(games[0].turns[0], HashMap::from([(String::from("blue"), 3), (String::from("red"), 4)]));
+        assert_eq!(games[0].turns[1], HashMap::from([(String::from("green"), 2)]));
+    }
+
+    #[test]
+    fn malformed_games_say_where() {
+        let e = parse_all("Game 1: 3 blue\nGame 2 1 red", |c| lines(c, line_to_game)).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 7: expected \":\", found ' '");
+        let e = parse_all("Game 1: 3 blue,", |c| lines(c, line_to_game)).unwrap_err();
+        assert_eq!(e.to_string(), "line 1, column 16: expected a number, found end of input");
+    }
+}
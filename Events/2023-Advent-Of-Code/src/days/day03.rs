@@ -0,0 +1,122 @@
+use crate::libaoc::{to_lines, Answer, SolveError, Solution, TextGrid};
+use crate::libaoc::coord::Coord;
+use crate::libaoc::find::GridMatch;
+use regex::Regex;
+use std::fmt::Debug;
+
+pub struct Day03;
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+/// Whether any cell touching the match, diagonals included, is a symbol.
+fn next_to_symbol(grid: &TextGrid<SerialNumber>, m: &GridMatch) -> bool {
+    let top_left = Coord::new(m.start.line.saturating_sub(1), m.start.offset.saturating_sub(1));
+    let bottom_right = Coord::new(m.end.line + 1, m.end.offset + 1);
+    let around = grid.get_cells(top_left, bottom_right);
+    around.iter().any(|gc| is_symbol(gc.value))
+}
+
+#[derive(Clone)]
+#[derive(PartialEq)]
+#[derive(Eq)]
+#[derive(Hash)]
+struct SerialNumber {
+    number: u32
+}
+
+impl Debug for SerialNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "|{}|", &self.number)
+    }
+}
+
+#[derive(Debug)]
+struct Gear {
+    first : u32,
+    second: u32
+}
+
+impl Gear {
+    fn ratio(&self) -> u64 {
+        self.first as u64 * self.second as u64
+    }
+}
+
+/// Attaches every part number, i.e. every number next to a symbol, to the
+/// cells it covers. Fails on a part number too big for a u32.
+fn scan_and_augment_grid(grid: &mut TextGrid<SerialNumber>) -> Result<(), SolveError> {
+    let re = Regex::new("([0-9]+)").unwrap();
+    let parts: Vec<GridMatch> = grid.find_all(&re).into_iter()
+        .filter(|m| next_to_symbol(grid, m))
+        .collect();
+    if let Some(m) = parts.iter().find(|m| m.text.parse::<u32>().is_err()) {
+        return Err(SolveError::Failed(format!("part number {} at {} does not fit in a u32", m.text, m.start)));
+    }
+    grid.attach_all(&parts, |_, m| m.text.parse().ok().map(|number| SerialNumber { number }))?;
+    Ok(())
+}
+
+fn scan_for_gears(grid: &TextGrid<SerialNumber>) -> Vec<Gear>{
+    let mut gears : Vec<Gear> = vec![];
+
+    for pivot in grid.find_char(|c| c == '*') {
+        let entities = grid.entities_in(grid.window(pivot.start, 1));
+        // println!("ENTITIES FOUND AROUND {:?}: {:?}", pivot, entities);
+        if entities.len() >= 2 {
+            gears.push(Gear { first: entities[0].value.number, second: entities[1].value.number })
+        }
+    }
+
+    gears
+}
+
+impl Solution for Day03 {
+    fn day(&self) -> u32 { 3 }
+
+    fn part_one(&self, input: &str) -> Answer {
+        let mut grid: TextGrid<SerialNumber> = TextGrid::from_lines(&to_lines(input));
+        scan_and_augment_grid(&mut grid)?;
+        let total : u64 = grid.entities.iter().map(|e| e.value.number as u64).sum();
+        Ok(total.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        let mut grid: TextGrid<SerialNumber> = TextGrid::from_lines(&to_lines(input));
+        scan_and_augment_grid(&mut grid)?;
+        // println!("{:?}", grid);
+        // println!("@(1,3): {:?}", grid.cells[1][3]);
+
+        let gears = scan_for_gears(&grid);
+        let total_ratios : u64 = gears.iter().map(|g| g.ratio()).sum();
+        Ok(total_ratios.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gears_on_the_edge_of_the_grid() {
+        assert_eq!(Day03.part_two("*12\n3..").as_deref(), Ok("36"));
+        assert_eq!(Day03.part_two("..4\n.5*").as_deref(), Ok("20"));
+    }
+
+    #[test]
+    fn equal_part_numbers_still_make_a_gear() {
+        assert_eq!(Day03.part_two("12*12").as_deref(), Ok("144"));
+        assert_eq!(Day03.part_two("7..\n.*.\n..7").as_deref(), Ok("49"));
+    }
+
+    #[test]
+    fn oversized_part_numbers_are_errors() {
+        let expected = SolveError::Failed(String::from("part number 99999999999 at (1, 1) does not fit in a u32"));
+        assert_eq!(Day03.part_one("*..\n.99999999999"), Err(expected.clone()));
+        assert_eq!(Day03.part_two("*..\n.99999999999"), Err(expected));
+        // Numbers away from any symbol are not part numbers, so never read.
+        assert_eq!(Day03.part_one("1.......\n..99999999999").as_deref(), Ok("0"));
+        assert_eq!(Day03.part_two("4294967295*4294967295").as_deref(), Ok("18446744065119617025"));
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use crate::libaoc::{Answer, Solution};
+use crate::libaoc::parse::{lines, parse_all, spaced, Cursor, ParseError};
+
+pub struct Day04;
+
+#[derive(Debug)]
+struct Card {
+    // id: u32,
+    // numbers: Vec<u32>,
+    // winners: Vec<u32>,
+    matches: u32,
+    points: u32,
+}
+
+fn points_from_matches(m:u32) -> u32{
+    match m {
+        0 => 0,
+        1 => 1,
+        _ => 2*points_from_matches(m-1)
+    }
+}
+
+impl Card {
+    fn from_numbers(numbers: Vec<u32>, winners: Vec<u32>) -> Card {
+        let n_set: HashSet<u32> = HashSet::from_iter(numbers);
+        let w_set: HashSet<u32> = HashSet::from_iter(winners);
+        let intersection: HashSet<_> = n_set.intersection(&w_set).collect();
+        Card {
+            // id: id,
+            // numbers: numbers,
+            // winners: winners,
+            matches: intersection.len() as u32,
+            points: points_from_matches(intersection.len() as u32)
+        }
+
+    }
+}
+
+fn parse_card(c: &mut Cursor) -> Result<Card, ParseError> {
+    // Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+    c.literal("Card")?;
+    c.gap()?;
+    let _id: u32 = c.number()?;
+    c.literal(":")?;
+    c.spaces();
+    let numbers = spaced(c, |c| c.number())?;
+    c.spaces();
+    c.literal("|")?;
+    c.spaces();
+    let winners = spaced(c, |c| c.number())?;
+    Ok(Card::from_numbers(numbers, winners))
+}
+
+fn total_points(cards: &[Card])  -> u32 {
+    let points = cards.iter()
+        .map(|c| c.points)
+        .sum();
+    points
+}
+
+/// Copies won past the end of the table are dropped, since those cards do
+/// not exist.
+fn card_count(cards: &[Card]) -> u32 {
+    let mut acc: usize = 0;
+    let mut counts = vec![1; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        acc += counts[i];
+        let won = (i+1)..(i+1+card.matches as usize).min(cards.len());
+        for ix in won {
+            counts[ix] += counts[i];
+        }
+    }
+    acc as u32
+}
+
+fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_all(input, |c| lines(c, parse_card))
+}
+
+impl Solution for Day04 {
+    fn day(&self) -> u32 { 4 }
+
+    fn part_one(&self, input: &str) -> Answer {
+        Ok(total_points(&parse_cards(input)?).to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        Ok(card_count(&parse_cards(input)?).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn example() {
+        let cards = parse_cards(EXAMPLE).unwrap();
+        assert_eq!(total_points(&cards), 13);
+        assert_eq!(card_count(&cards), 30);
+    }
+
+    #[test]
+    fn wins_past_the_last_card_are_dropped() {
+        let cards = parse_cards("Card 1: 41 48 | 41 48").unwrap();
+        assert_eq!((total_points(&cards), card_count(&cards)), (2, 1));
+        let cards = parse_cards("Card 1: 41 48 | 41 48\nCard 2: 1 | 2").unwrap();
+        assert_eq!(card_count(&cards), 3);
+    }
+
+    #[test]
+    fn malformed_cards_are_errors() {
+        let error = |input| parse_cards(input).unwrap_err().to_string();
+        assert_eq!(error("Card 1: 41 48 41 48"), "line 1, column 20: expected \"|\", found end of input");
+        assert_eq!(error("Card 1 41 48 | 41 48"), "line 1, column 7: expected \":\", found ' '");
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::libaoc::{Answer, SolveError, Solution};
+use crate::libaoc::intervals::IntervalSet;
+use crate::libaoc::parse::{key_value, lines, spaced, Cursor, ParseError};
+use crate::libaoc::sections::{SectionError, Sections};
+
+pub struct Day05;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub out_start: u64,
+    pub in_start: u64,
+    pub range: u64,
+}
+
+impl Mapping {
+    fn in_end(&self) -> u64 {
+        self.in_start + self.range
+    }
+
+    fn out_end(&self) -> u64 {
+        self.out_start + self.range
+    }
+}
+
+#[derive(Debug)]
+pub struct GardenMap{
+    pub input: String,
+    pub output: String,
+    pub mappings: Vec<Mapping>
+}
+
+impl Clone for GardenMap {
+    fn clone(&self) -> Self {
+        Self {
+            input: self.input.clone(),
+            output: self.output.clone(),
+            mappings: self.mappings.clone()
+        }
+    }
+}
+
+
+impl GardenMap {
+    /// Reads a whole map:
+    ///
+    /// ```text
+    /// seed-to-soil map:
+    /// 50 98 2
+    /// 52 50 48
+    /// ```
+    pub fn parse(c: &mut Cursor) -> Result<GardenMap, ParseError> {
+        let input = c.word()?;
+        c.literal("-to-")?;
+        let output = c.word()?;
+        c.gap()?;
+        c.literal("map:")?;
+        // A map with no ranges passes every value through unchanged.
+        if c.at_end() { return Ok(GardenMap { input, output, mappings: vec![] }) }
+        c.newline()?;
+        let mappings = lines(c, |c| {
+            let start = *c;
+            let out_start: u64 = c.number()?;
+            c.gap()?;
+            let in_start: u64 = c.number()?;
+            c.gap()?;
+            let range = c.number()?;
+            // Ends are exclusive, so they must still fit in a u64.
+            if out_start.checked_add(range).is_none() || in_start.checked_add(range).is_none() {
+                let mut e = start.error("ranges that end within u64");
+                e.found = format!("{} {} {}", out_start, in_start, range);
+                return Err(e);
+            }
+            Ok(Mapping { out_start, in_start, range })
+        })?;
+        Ok(GardenMap { input, output, mappings })
+    }
+
+    pub fn map(&self, i: u64) -> u64 {
+        // println!("-> {}", i);
+        for m in &self.mappings {
+            if i >= m.in_start && i < m.in_start+m.range {
+                return m.out_start + (i-m.in_start)
+            }
+        }
+        i
+    }
+
+    /// Maps every value of `input` at once. Ranges are split wherever they
+    /// cross a mapping boundary; values outside all mappings map to themselves.
+    pub fn map_ranges(&self, input: &IntervalSet) -> IntervalSet {
+        let mut unmapped = input.clone();
+        let mut out = IntervalSet::new();
+        for m in &self.mappings {
+            let source = IntervalSet::from(m.in_start..m.in_start+m.range);
+            let hit = unmapped.intersection(&source);
+            for r in hit.ranges() {
+                out.insert(m.out_start + (r.start-m.in_start)..m.out_start + (r.end-m.in_start));
+            }
+            unmapped = unmapped.difference(&source);
+        }
+        out.union(&unmapped)
+    }
+
+    /// The map as a sorted list of pieces covering the whole `u64` domain:
+    /// the effective part of each mapping (earlier mappings win on overlap)
+    /// plus identity pieces for the gaps in between. `u64::MAX` itself is
+    /// left out: no range can end past it, so it always maps to itself.
+    fn pieces(&self) -> Vec<Mapping> {
+        let mut covered = IntervalSet::new();
+        let mut pieces = vec![];
+        for m in &self.mappings {
+            let source = IntervalSet::from(m.in_start..m.in_end());
+            for r in source.difference(&covered).ranges() {
+                pieces.push(Mapping {
+                    out_start: m.out_start + (r.start-m.in_start),
+                    in_start: r.start,
+                    range: r.end - r.start
+                });
+            }
+            covered = covered.union(&source);
+        }
+        for r in IntervalSet::from(0..u64::MAX).difference(&covered).ranges() {
+            pieces.push(Mapping { out_start: r.start, in_start: r.start, range: r.end - r.start });
+        }
+        pieces.sort_by_key(|p| p.in_start);
+        pieces
+    }
+
+    fn from_pieces(input: &str, output: &str, pieces: Vec<Mapping>) -> GardenMap {
+        GardenMap {
+            input: String::from(input),
+            output: String::from(output),
+            // Identity is the default behaviour of `map`, so it needs no entry.
+            mappings: pieces.into_iter().filter(|p| p.in_start != p.out_start).collect()
+        }
+    }
+
+    /// A single map equivalent to applying `self` then `next`, or `None` if
+    /// `next` does not start from the category `self` maps to.
+    pub fn compose(&self, next: &GardenMap) -> Option<GardenMap> {
+        if self.output != next.input { return None }
+        let mut pieces = vec![];
+        let second_pieces = next.pieces();
+        for first in self.pieces() {
+            for second in &second_pieces {
+                let lo = first.out_start.max(second.in_start);
+                let hi = first.out_end().min(second.in_end());
+                if lo < hi {
+                    pieces.push(Mapping {
+                        out_start: second.out_start + (lo-second.in_start),
+                        in_start: first.in_start + (lo-first.out_start),
+                        range: hi - lo
+                    });
+                }
+            }
+        }
+        pieces.sort_by_key(|p| p.in_start);
+        Some(GardenMap::from_pieces(&self.input, &next.output, pieces))
+    }
+
+    /// Every input value that maps into `outputs`.
+    pub fn preimage(&self, outputs: &IntervalSet) -> IntervalSet {
+        let mut inputs = IntervalSet::new();
+        for p in self.pieces() {
+            let hit = outputs.intersection(&IntervalSet::from(p.out_start..p.out_end()));
+            for r in hit.ranges() {
+                inputs.insert(p.in_start + (r.start-p.out_start)..p.in_start + (r.end-p.out_start));
+            }
+        }
+        inputs
+    }
+
+    /// The reverse map (e.g. location-to-seed), or `None` when two inputs
+    /// share an output and there is no single answer to map back to.
+    pub fn invert(&self) -> Option<GardenMap> {
+        let mut pieces = self.pieces();
+        pieces.sort_by_key(|p| p.out_start);
+        if pieces.windows(2).any(|w| w[0].out_end() > w[1].out_start) {
+            return None
+        }
+        let inverted = pieces.into_iter()
+            .map(|p| Mapping { out_start: p.in_start, in_start: p.out_start, range: p.range })
+            .collect();
+        Some(GardenMap::from_pieces(&self.output, &self.input, inverted))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlmanacError {
+    UnknownCategory(String),
+    Unreachable { from: String, to: String },
+    /// Categories along a cycle, starting and ending with the same one.
+    Cycle(Vec<String>),
+    Input(SectionError),
+}
+
+impl From<SectionError> for AlmanacError {
+    fn from(e: SectionError) -> AlmanacError {
+        AlmanacError::Input(e)
+    }
+}
+
+impl From<AlmanacError> for SolveError {
+    fn from(e: AlmanacError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacError::UnknownCategory(c) => write!(f, "no map starts or ends at category '{}'", c),
+            AlmanacError::Unreachable { from, to } => write!(f, "no chain of maps leads from '{}' to '{}'", from, to),
+            AlmanacError::Cycle(path) => write!(f, "maps form a cycle: {}", path.join(" -> ")),
+            AlmanacError::Input(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// The seeds plus every map, viewed as a directed graph whose nodes are
+/// categories and whose edges are the maps between them. A category may
+/// have any number of outgoing maps.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<GardenMap>,
+    edges: HashMap<String, Vec<usize>>,
+}
+
+impl Almanac {
+    /// Builds the category graph, rejecting maps that form a cycle.
+    pub fn new(seeds: Vec<u64>, maps: Vec<GardenMap>) -> Result<Almanac, AlmanacError> {
+        let mut edges: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, m) in maps.iter().enumerate() {
+            edges.entry(m.input.clone()).or_default().push(i);
+        }
+        let almanac = Almanac { seeds, maps, edges };
+        if let Some(cycle) = almanac.find_cycle() {
+            return Err(AlmanacError::Cycle(cycle));
+        }
+        Ok(almanac)
+    }
+
+    pub fn categories(&self) -> HashSet<&str> {
+        self.maps.iter()
+            .flat_map(|m| [m.input.as_str(), m.output.as_str()])
+            .collect()
+    }
+
+    fn outgoing(&self, category: &str) -> impl Iterator<Item = &GardenMap> {
+        self.edges.get(category).into_iter().flatten().map(|i| &self.maps[*i])
+    }
+
+    fn find_cycle(&self) -> Option<Vec<String>> {
+        // Iterative depth-first search; a map leading back into a category
+        // that is still on the stack closes a cycle.
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut starts = self.maps.iter().map(|m| m.input.as_str()).collect::<Vec<_>>();
+        starts.sort();
+        for start in starts {
+            if done.contains(start) { continue }
+            let mut stack: Vec<(&str, Vec<&GardenMap>)> = vec![(start, self.outgoing(start).collect())];
+            while let Some((category, pending)) = stack.last_mut() {
+                let category = *category;
+                match pending.pop() {
+                    Some(m) => {
+                        let next = m.output.as_str();
+                        if let Some(pos) = stack.iter().position(|(c, _)| *c == next) {
+                            let mut cycle = stack[pos..].iter().map(|(c, _)| c.to_string()).collect::<Vec<_>>();
+                            cycle.push(next.to_string());
+                            return Some(cycle);
+                        }
+                        if !done.contains(next) {
+                            stack.push((next, self.outgoing(next).collect()));
+                        }
+                    },
+                    None => {
+                        done.insert(category);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// The shortest chain of maps leading from `from` to `to`. When several
+    /// chains are equally short, maps listed earlier in the almanac win.
+    pub fn route(&self, from: &str, to: &str) -> Result<Vec<&GardenMap>, AlmanacError> {
+        let categories = self.categories();
+        for c in [from, to] {
+            if !categories.contains(c) {
+                return Err(AlmanacError::UnknownCategory(c.to_string()));
+            }
+        }
+
+        let mut via: HashMap<&str, &GardenMap> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(category) = queue.pop_front() {
+            if category == to { break }
+            for m in self.outgoing(category) {
+                let next = m.output.as_str();
+                if next != from && !via.contains_key(next) {
+                    via.insert(next, m);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut path = vec![];
+        let mut current = to;
+        while current != from {
+            let m = via.get(current).ok_or(AlmanacError::Unreachable { from: from.to_string(), to: to.to_string() })?;
+            path.push(*m);
+            current = m.input.as_str();
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    pub fn map_x_to_y(&self, input: &str, output: &str, value: u64) -> Result<u64, AlmanacError> {
+        Ok(self.route(input, output)?.iter().fold(value, |i, m| m.map(i)))
+    }
+
+    pub fn map_ranges_x_to_y(&self, input: &str, output: &str, values: IntervalSet) -> Result<IntervalSet, AlmanacError> {
+        Ok(self.route(input, output)?.iter().fold(values, |set, m| m.map_ranges(&set)))
+    }
+
+    /// Composes the chain of maps from `input` to `output` into a single map.
+    pub fn flatten(&self, input: &str, output: &str) -> Result<GardenMap, AlmanacError> {
+        let route = self.route(input, output)?;
+        let identity = GardenMap { input: input.to_string(), output: input.to_string(), mappings: vec![] };
+        Ok(route.iter().fold(identity, |flat, m| flat.compose(m).unwrap()))
+    }
+
+    /// The seeds read as start and length pairs, as part 2 does. Fails on an
+    /// odd number of seeds, which leaves the last range without a length.
+    pub fn seed_ranges(&self) -> Result<IntervalSet, SolveError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(SolveError::Failed(format!("seed ranges need a start and a length each, found {} numbers", self.seeds.len())));
+        }
+        Ok(self.seeds.chunks(2).map(|rdef| rdef[0]..rdef[0]+rdef[1]).collect())
+    }
+}
+
+fn parse_garden(s:&str) -> Result<Almanac, AlmanacError> {
+    let mut sections = Sections::read(s);
+    let (seeds, maps) = (sections.next("seeds")?, sections.rest("map", 1)?);
+    let seeds = seeds.parse(|c| key_value(c, "seeds", |c| {
+        let start = *c;
+        let seeds: Vec<u64> = spaced(c, |c| c.number())?;
+        // Part 2 reads the seeds as start and length pairs.
+        if let Some(pair) = seeds.chunks(2).find(|p| p.len() == 2 && p[0].checked_add(p[1]).is_none()) {
+            let mut e = start.error("seed ranges that end within u64");
+            e.found = format!("{} {}", pair[0], pair[1]);
+            return Err(e);
+        }
+        Ok(seeds)
+    }))?;
+    let maps = maps.iter()
+        .map(|sec| sec.parse(GardenMap::parse))
+        .collect::<Result<Vec<GardenMap>, _>>()?;
+    Almanac::new(seeds, maps)
+}
+
+impl Solution for Day05 {
+    fn day(&self) -> u32 { 5 }
+
+    fn part_one(&self, input: &str) -> Answer {
+        let almanac = parse_garden(input)?;
+        let locations = almanac.seeds.iter()
+            .map(|seed| almanac.map_x_to_y("seed", "location", *seed))
+            .collect::<Result<Vec<u64>, _>>()?;
+        locations.iter().min().map(|l| l.to_string()).ok_or(SolveError::Failed(String::from("no seeds")))
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        let almanac = parse_garden(input)?;
+        let seed_to_location = almanac.flatten("seed", "location")?;
+        seed_to_location.map_ranges(&almanac.seed_ranges()?).min().map(|l| l.to_string()).ok_or(SolveError::Failed(String::from("no seeds")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::input::read_input;
+    use crate::libaoc::parse::parse_all;
+
+    fn example() -> Almanac {
+        parse_garden(&read_input("files/05-example.txt").unwrap()).unwrap()
+    }
+
+    fn map(spec: &str) -> GardenMap {
+        parse_all(spec, GardenMap::parse).unwrap()
+    }
+
+    #[test]
+    fn sections_survive_windows_line_endings() {
+        let unix = read_input("files/05-example.txt").unwrap();
+        let windows = unix.replace('\n', "  \r\n");
+        assert_eq!(parse_garden(&windows).unwrap().maps.len(), example().maps.len());
+        let e = parse_garden("seeds: 79 14\r\n").unwrap_err();
+        assert_eq!(e.to_string(), "expected at least 2 sections separated by blank lines, found 1");
+        let e = parse_garden("seeds: 79 14\n\nseed-to-soil map:\n50 98\n").unwrap_err();
+        assert_eq!(e.to_string(), "in map 1: line 4, column 6: expected a space, found end of input");
+    }
+
+    #[test]
+    fn ranges_past_the_end_of_u64_are_rejected() {
+        let e = parse_garden("seeds: 1 2\n\na-to-b map:\n0 5 7\n0 18446744073709551615 1\n").unwrap_err();
+        assert_eq!(e.to_string(), "in map 1: line 5, column 1: expected ranges that end within u64, found 0 18446744073709551615 1");
+        let e = parse_garden("seeds: 18446744073709551615 1\n\na-to-b map:\n0 5 7\n").unwrap_err();
+        assert_eq!(e.to_string(), "in seeds: line 1, column 8: expected seed ranges that end within u64, found 18446744073709551615 1");
+
+        let swap = map("a-to-b map:\n0 18446744073709551614 1\n18446744073709551614 0 1");
+        assert_eq!((swap.map(u64::MAX - 1), swap.map(0), swap.map(u64::MAX)), (0, u64::MAX - 1, u64::MAX));
+        let round_trip = swap.compose(&swap.invert().unwrap()).unwrap();
+        assert!(round_trip.mappings.is_empty());
+    }
+
+    #[test]
+    fn range_mapping_agrees_with_mapping_every_seed() {
+        let almanac = example();
+        let ranges = almanac.seed_ranges().unwrap();
+        let mapped = almanac.map_ranges_x_to_y("seed", "location", ranges.clone()).unwrap();
+
+        let brute: IntervalSet = ranges.ranges().iter()
+            .flat_map(|r| r.clone())
+            .map(|seed| almanac.map_x_to_y("seed", "location", seed).unwrap())
+            .map(|l| l..l+1)
+            .collect();
+        assert_eq!(mapped, brute);
+        assert_eq!(mapped.len(), ranges.len());
+    }
+
+    #[test]
+    fn flattened_chain_maps_like_walking_the_chain() {
+        let almanac = example();
+        let flat = almanac.flatten("seed", "location").unwrap();
+        assert_eq!(flat.input, "seed");
+        assert_eq!(flat.output, "location");
+        for seed in 0..200 {
+            assert_eq!(Ok(flat.map(seed)), almanac.map_x_to_y("seed", "location", seed));
+        }
+    }
+
+    #[test]
+    fn compose_requires_matching_categories() {
+        let almanac = example();
+        let seed_to_soil = almanac.route("seed", "soil").unwrap()[0];
+        let water_to_light = almanac.route("water", "light").unwrap()[0];
+        assert!(seed_to_soil.compose(water_to_light).is_none());
+    }
+
+    #[test]
+    fn preimage_finds_every_seed_landing_in_a_location_range() {
+        let flat = example().flatten("seed", "location").unwrap();
+        let locations = IntervalSet::from(40..50);
+        let seeds = flat.preimage(&locations);
+        for seed in 0..200 {
+            assert_eq!(seeds.contains(seed), locations.contains(flat.map(seed)), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn invert_undoes_a_permutation_and_rejects_collisions() {
+        let swap = map("a-to-b map:\n10 20 5\n20 10 5");
+        let back = swap.invert().unwrap();
+        assert_eq!((back.input.as_str(), back.output.as_str()), ("b", "a"));
+        for i in 0..40 {
+            assert_eq!(back.map(swap.map(i)), i);
+        }
+
+        let collide = map("a-to-b map:\n0 10 5");
+        assert!(collide.invert().is_none());
+    }
+
+    #[test]
+    fn routes_between_any_two_categories() {
+        let almanac = example();
+        let route = almanac.route("soil", "light").unwrap();
+        let hops = route.iter().map(|m| m.output.as_str()).collect::<Vec<_>>();
+        assert_eq!(hops, vec!["fertilizer", "water", "light"]);
+        assert!(almanac.route("seed", "seed").unwrap().is_empty());
+    }
+
+    #[test]
+    fn routes_through_branching_categories() {
+        let maps = vec![
+            map("a-to-b map:\n0 10 5"),
+            map("a-to-c map:\n100 0 5"),
+            map("c-to-d map:\n0 100 1"),
+            map("b-to-d map:"),
+        ];
+        let almanac = Almanac::new(vec![], maps).unwrap();
+        assert_eq!(almanac.map_x_to_y("a", "c", 1), Ok(101));
+        assert_eq!(almanac.map_x_to_y("a", "d", 10), Ok(0));
+        assert_eq!(almanac.route("a", "d").unwrap()[0].output, "b");
+    }
+
+    #[test]
+    fn unreachable_and_unknown_categories_are_errors() {
+        let almanac = example();
+        assert_eq!(
+            almanac.route("location", "seed").unwrap_err(),
+            AlmanacError::Unreachable { from: String::from("location"), to: String::from("seed") });
+        assert_eq!(
+            almanac.route("seed", "banana").unwrap_err(),
+            AlmanacError::UnknownCategory(String::from("banana")));
+    }
+
+    #[test]
+    fn parts_report_almanac_errors() {
+        let no_location = "seeds: 1 2\n\nseed-to-soil map:\n0 5 7\n";
+        let unreachable = SolveError::Failed(String::from("no map starts or ends at category 'location'"));
+        assert_eq!(Day05.part_one(no_location), Err(unreachable.clone()));
+        assert_eq!(Day05.part_two(no_location), Err(unreachable));
+        let backwards = "seeds: 1 2\n\nlocation-to-seed map:\n0 5 7\n";
+        let expected = SolveError::Failed(String::from("no chain of maps leads from 'seed' to 'location'"));
+        assert_eq!(Day05.part_one(backwards), Err(expected.clone()));
+        assert_eq!(Day05.part_two(backwards), Err(expected));
+
+        let odd = "seeds: 79 14 55\n\nseed-to-location map:\n0 5 7\n";
+        assert_eq!(Day05.part_one(odd), Ok(String::from("14")));
+        assert_eq!(Day05.part_two(odd), Err(SolveError::Failed(String::from("seed ranges need a start and a length each, found 3 numbers"))));
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let maps = vec![
+            map("a-to-b map:\n0 10 5"),
+            map("b-to-c map:\n0 10 5"),
+            map("c-to-a map:\n0 10 5"),
+        ];
+        let err = Almanac::new(vec![], maps).unwrap_err();
+        assert_eq!(err, AlmanacError::Cycle(vec!["a", "b", "c", "a"].into_iter().map(String::from).collect()));
+    }
+}
@@ -0,0 +1,194 @@
+use std::ops::Range;
+
+use itertools::Itertools;
+
+use crate::libaoc::{Answer, Solution};
+use crate::libaoc::parse::{key_value, parse_all, spaced, ParseError};
+
+pub struct Day06;
+
+struct Race {
+    duration: u64,
+    best: u64
+}
+
+impl Race {
+    const fn from(d: u64, b: u64) -> Race {
+        Race{
+            duration: d,
+            best: b
+        }
+    }
+
+    /// Hold times that beat the record. Holding for `t` travels
+    /// `t*(duration-t)`, so the winners lie strictly between the roots of
+    /// `t^2 - duration*t + best = 0`. The float-free integer square root
+    /// gives a starting point that is then nudged onto the exact boundary.
+    fn winning_holds(&self) -> Range<u64> {
+        let (d, b) = (self.duration as u128, self.best as u128);
+        if d*d <= 4*b { return 0..0 }
+        let beats = |t: u128| t*(d-t) > b;
+
+        let mut lo = (d - (d*d - 4*b).isqrt()) / 2;
+        while lo > 0 && beats(lo-1) { lo -= 1 }
+        while lo <= d/2 && !beats(lo) { lo += 1 }
+        if lo > d/2 { return 0..0 }
+        // Distances are symmetric around d/2.
+        (lo as u64)..((d-lo+1) as u64)
+    }
+
+    fn ways_to_win(&self) -> u64 {
+        let holds = self.winning_holds();
+        holds.end - holds.start
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[derive(Debug)]
+struct RaceSolution {
+    accelerate_time: u64,
+    total_distance:u64
+}
+
+#[cfg(test)]
+fn distance(speed:u64 , time:u64)->u64{
+    speed * time
+}
+
+#[cfg(test)]
+fn gen_solutions(race_time: u64) -> Vec<RaceSolution>{
+    let mut sols : Vec<RaceSolution> = vec![];
+    for acc_time in 0..race_time{
+        sols.push(RaceSolution {
+            accelerate_time: acc_time,
+            total_distance: distance(acc_time, race_time-acc_time)
+        })
+    }
+    sols
+}
+
+#[cfg(test)]
+fn race_to_good_sols(r: &Race) -> Vec<RaceSolution> {
+    let sols = gen_solutions(r.duration);
+    sols.into_iter()
+        .filter(|sol| sol.total_distance > r.best)
+        .collect_vec()
+}
+
+/// Both lines of the sheet, which must hold the same number of races. A
+/// mismatch is reported at the end of the shorter line.
+fn parse_sheet(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    // Time:      7  15   30
+    // Distance:  9  40  200
+    parse_all(input, |c| {
+        let durations = key_value(c, "Time", |c| spaced(c, |c| c.number()))?;
+        let time_end = *c;
+        c.newline()?;
+        let bests = key_value(c, "Distance", |c| spaced(c, |c| c.number()))?;
+        if durations.len() != bests.len() {
+            let (shorter, found, wanted) = if durations.len() < bests.len() {
+                (time_end, durations.len(), bests.len())
+            } else {
+                (*c, bests.len(), durations.len())
+            };
+            let mut e = shorter.error(&format!("{} numbers", wanted));
+            e.found = found.to_string();
+            return Err(e)
+        }
+        Ok((durations, bests))
+    })
+}
+
+fn parse_races(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (durations, bests) = parse_sheet(input)?;
+    Ok(durations.into_iter().zip(bests).map(|(d, b)| Race::from(d, b)).collect_vec())
+}
+
+fn parse_single_race(input: &str) -> Result<Race, ParseError> {
+    // Part 2 ignores the spaces between the numbers on each line, leaving
+    // one number on each.
+    parse_all(&input.replace([' ', '\t'], ""), |c| {
+        let duration = key_value(c, "Time", |c| c.number())?;
+        c.newline()?;
+        let best = key_value(c, "Distance", |c| c.number())?;
+        Ok(Race::from(duration, best))
+    })
+}
+
+impl Solution for Day06 {
+    fn day(&self) -> u32 { 6 }
+
+    fn part_one(&self, input: &str) -> Answer {
+        let races = parse_races(input)?;
+        Ok(races.iter().map(|r| r.ways_to_win()).product::<u64>().to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        Ok(parse_single_race(input)?.ways_to_win().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::SolveError;
+
+    fn brute_force_holds(r: &Race) -> Range<u64> {
+        let good = race_to_good_sols(r);
+        match (good.first(), good.last()) {
+            (Some(first), Some(last)) => first.accelerate_time..last.accelerate_time+1,
+            _ => 0..0
+        }
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(Race::from(7, 9).winning_holds(), 2..6);
+        assert_eq!(Race::from(15, 40).winning_holds(), 4..12);
+        assert_eq!(Race::from(30, 200).winning_holds(), 11..20);
+        assert_eq!(Race::from(71530, 940200).ways_to_win(), 71503);
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force() {
+        for duration in 0..60 {
+            for best in 0..(duration*duration/4 + 2) {
+                let race = Race::from(duration, best);
+                let holds = race.winning_holds();
+                let expected = brute_force_holds(&race);
+                assert_eq!(holds.end - holds.start, expected.end - expected.start, "race {} / {}", duration, best);
+                if !expected.is_empty() {
+                    assert_eq!(holds, expected, "race {} / {}", duration, best);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reads_the_sheet_both_ways() {
+        let sheet = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let races = parse_races(sheet).unwrap();
+        assert_eq!(races.iter().map(|r| (r.duration, r.best)).collect_vec(), vec![(7, 9), (15, 40), (30, 200)]);
+        let race = parse_single_race(sheet).unwrap();
+        assert_eq!((race.duration, race.best), (71530, 940200));
+        assert_eq!(parse_sheet("Time: 7\nDistnace: 9").unwrap_err().to_string(), "line 2, column 1: expected \"Distance\", found 'D'");
+    }
+
+    #[test]
+    fn lines_must_list_the_same_races() {
+        assert_eq!(parse_races("Time: 7 15\nDistance: 9").err().map(|e| e.to_string()), Some(String::from("line 2, column 12: expected 2 numbers, found 1")));
+        assert_eq!(parse_races("Time: 7\nDistance: 9 40\n").err().map(|e| e.to_string()), Some(String::from("line 1, column 8: expected 2 numbers, found 1")));
+        assert_eq!(Day06.part_one("Time: 7 15\nDistance: 9").unwrap_err(), SolveError::Failed(String::from("line 2, column 12: expected 2 numbers, found 1")));
+        // Joined up, each line is a single number whatever the counts.
+        let race = parse_single_race("Time: 7 15\nDistance: 9").unwrap();
+        assert_eq!((race.duration, race.best), (715, 9));
+    }
+
+    #[test]
+    fn ties_with_the_record_do_not_win() {
+        // Holding 5ms of 10 exactly matches a record of 25.
+        assert_eq!(Race::from(10, 25).ways_to_win(), 0);
+        assert_eq!(Race::from(10, 24).winning_holds(), 5..6);
+    }
+}
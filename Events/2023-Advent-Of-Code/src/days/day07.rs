@@ -0,0 +1,345 @@
+use std::{cmp::Ordering, collections::HashMap, marker::PhantomData};
+
+use itertools::Itertools;
+
+use crate::libaoc::{Answer, Solution};
+use crate::libaoc::parse::{lines, parse_all, Cursor, ParseError};
+
+pub struct Day07;
+
+/*
+
+CAMEL CARDS
+
+In Camel Cards, you get a list of hands, and your goal is to order them based
+on the strength of each hand. A hand consists of five cards labeled one of
+A, K, Q, J, T, 9, 8, 7, 6, 5, 4, 3, or 2. The relative strength of each card
+follows this order, where A is the highest and 2 is the lowest.
+
+Every hand is exactly one type. From strongest to weakest, they are:
+
+- Five of a kind, where all five cards have the same label: AAAAA
+- Four of a kind, where four cards have the same label and one card has a
+  different label: AA8AA
+- Full house, where three cards have the same label, and the remaining two
+  cards share a different label: 23332
+- Three of a kind, where three cards have the same label, and the remaining
+  two cards are each different from any other card in the hand: TTT98
+- Two pair, where two cards share one label, two other cards share a second
+  label, and the remaining card has a third label: 23432
+- One pair, where two cards share one label, and the other three cards have
+  a different label from the pair and each other: A23A4
+- High card, where all cards' labels are distinct: 23456
+
+Hands are primarily ordered based on type; for example, every full house is
+stronger than any three of a kind.
+
+If two hands have the same type, a second ordering rule takes effect. Start
+by comparing the first card in each hand. If these cards are different, the
+hand with the stronger first card is considered stronger. If the first card
+in each hand have the same label, however, then move on to considering the
+second card in each hand. If they differ, the hand with the higher second
+card wins; otherwise, continue with the third card in each hand, then the
+fourth, then the fifth.
+
+So, 33332 and 2AAAA are both four of a kind hands, but 33332 is stronger
+because its first card is stronger. Similarly, 77888 and 77788 are both a
+full house, but 77888 is stronger because its third card is stronger (and
+both hands have the same first and second card).
+*/
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandType {
+    FiveOfAKind = 7,
+    FourOfAKind = 6,
+    FullHouse = 5,
+    ThreeOfAKind = 4,
+    TwoPairs = 3,
+    OnePair = 2,
+    HighCard = 1
+}
+
+fn to_char_count(input: &str) -> HashMap<char,u32>{
+    input
+        .chars()
+        .fold(HashMap::new(), |mut map, c| {
+            *map.entry(c).or_insert(0) += 1;
+            map
+        })
+}
+
+/// The standard card labels, strongest first.
+const CARDS: &str = "AKQJT98765432";
+
+/// Standard strength of a card, from 14 for `A` down to 2 for `2`. Labels
+/// outside `CARDS` are worth 0.
+fn card_to_val(c: char) -> u32 {
+    CARDS.find(c).map_or(0, |i| (CARDS.len() - i) as u32 + 1)
+}
+
+/// Classifies a hand of at most five cards from the sizes of its two
+/// largest groups of equal cards.
+fn hand_type_from_groups(max_count: u32, second_count: u32) -> HandType {
+    if max_count == 5 { return HandType::FiveOfAKind }
+    if max_count == 4 { return HandType::FourOfAKind }
+    if max_count == 3 && second_count >= 2 { return HandType::FullHouse }
+    if max_count == 3 { return HandType::ThreeOfAKind; }
+    if max_count == 2 && second_count == 2 {return HandType::TwoPairs; }
+    if max_count == 2 { return HandType::OnePair; }
+    HandType::HighCard
+}
+
+/// A variant of Camel Cards. Only `card_value` is required; wild cards,
+/// hand size and classification all have the standard behaviour by default.
+pub trait Rules {
+    /// Cards in a hand. The standard `hand_type` only knows hands of up to
+    /// five cards, so rules with bigger hands must classify them themselves.
+    const HAND_SIZE: usize = 5;
+
+    /// Every label a card may have; hands with any other are rejected when
+    /// parsed.
+    const LABELS: &str = CARDS;
+
+    /// Strength of a single card, used to break ties between hands of the
+    /// same type. Higher is stronger.
+    fn card_value(c: char) -> u32;
+
+    /// Wild cards join whichever group makes the strongest hand.
+    fn is_wild(_c: char) -> bool {
+        false
+    }
+
+    fn hand_type(cards: &str) -> HandType {
+        const { assert!(Self::HAND_SIZE <= 5, "hands of more than five cards need their own hand_type") }
+        let mut charcounts = to_char_count(cards);
+        let wilds: u32 = charcounts.iter().filter(|(c, _)| Self::is_wild(**c)).map(|(_, n)| n).sum();
+        charcounts.retain(|c, _| !Self::is_wild(*c));
+        let counts = charcounts.values().sorted().rev().collect_vec();
+        let max_count = counts.first().map_or(0, |n| **n) + wilds;
+        let second_count = counts.get(1).map_or(0, |n| **n);
+        hand_type_from_groups(max_count, second_count)
+    }
+}
+
+/// Part 1: every card stands for itself, `A` high.
+pub struct Standard;
+
+impl Rules for Standard {
+    fn card_value(c: char) -> u32 {
+        card_to_val(c)
+    }
+}
+
+/// Part 2: `J` is a joker, wild but the weakest card on its own.
+pub struct JokersWild;
+
+impl Rules for JokersWild {
+    fn card_value(c: char) -> u32 {
+        match c {
+            'J' => 1,
+            _ => card_to_val(c)
+        }
+    }
+
+    fn is_wild(c: char) -> bool {
+        c == 'J'
+    }
+}
+
+#[derive(Debug)]
+pub struct Hand<R: Rules> {
+    cards: String,
+    bid: u32,
+    hand_type: HandType,
+    rules: PhantomData<R>
+}
+
+impl<R: Rules> Hand<R> {
+    fn parse(c: &mut Cursor) -> Result<Hand<R>, ParseError> {
+        // 32T3K 765
+        let start = *c;
+        let cards = c.word()?;
+        if cards.chars().count() != R::HAND_SIZE || !cards.chars().all(|ch| R::LABELS.contains(ch)) {
+            let mut e = start.error(&format!("{} cards out of {}", R::HAND_SIZE, R::LABELS));
+            e.found = format!("{:?}", cards);
+            return Err(e);
+        }
+        c.gap()?;
+        let bid = c.number()?;
+        Ok(Hand {
+            hand_type: R::hand_type(&cards),
+            cards,
+            bid,
+            rules: PhantomData
+        })
+    }
+}
+
+impl<R: Rules> PartialEq for Hand<R> {
+    /// Hands are equal when they rank the same, which under some rules
+    /// happens for different cards.
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<R: Rules> Eq for Hand<R> {}
+
+impl<R: Rules> PartialOrd for Hand<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: Rules> Ord for Hand<R> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let me = self.hand_type as isize;
+        let oth = other.hand_type as isize;
+        if me > oth { return Ordering::Greater}
+        if me < oth { return Ordering::Less}
+        for (me_c, oth_c) in self.cards.chars().zip(other.cards.chars()){
+            if R::card_value(me_c) > R::card_value(oth_c) {return Ordering::Greater;}
+            if R::card_value(me_c) < R::card_value(oth_c) {return Ordering::Less;}
+        }
+        Ordering::Equal
+    }
+}
+
+fn total_winnings<R: Rules>(hands: &mut [Hand<R>]) ->u32 {
+    hands.sort();
+    let mut acc = 0;
+    for (i, h) in hands.iter().enumerate() {
+        acc += ((i as u32)+1)*h.bid;
+    }
+    acc
+}
+
+fn winnings_under<R: Rules>(input: &str) -> Result<u32, ParseError> {
+    let mut hands = parse_all(input, |c| lines(c, Hand::<R>::parse))?;
+    Ok(total_winnings(&mut hands))
+}
+
+impl Solution for Day07 {
+    fn day(&self) -> u32 { 7 }
+
+    fn part_one(&self, input: &str) -> Answer {
+        Ok(winnings_under::<Standard>(input)?.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        Ok(winnings_under::<JokersWild>(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand<R: Rules>(line: &str) -> Hand<R> {
+        parse_all(line, Hand::parse).unwrap()
+    }
+
+    /// Aces count as one and both jokers and deuces are wild.
+    struct AcesLowDeucesWild;
+
+    impl Rules for AcesLowDeucesWild {
+        fn card_value(c: char) -> u32 {
+            match c {
+                'A' => 1,
+                _ => card_to_val(c)
+            }
+        }
+
+        fn is_wild(c: char) -> bool {
+            c == '2' || c == 'J'
+        }
+    }
+
+    struct ThreeCardHands;
+
+    impl Rules for ThreeCardHands {
+        const HAND_SIZE: usize = 3;
+
+        fn card_value(c: char) -> u32 {
+            card_to_val(c)
+        }
+    }
+
+    /// The standard deck plus a `W` that is wild and the weakest card.
+    struct WithWildcard;
+
+    impl Rules for WithWildcard {
+        const LABELS: &str = "AKQJT98765432W";
+
+        fn card_value(c: char) -> u32 {
+            card_to_val(c)
+        }
+
+        fn is_wild(c: char) -> bool {
+            c == 'W'
+        }
+    }
+
+    /// Every face card is worth the same.
+    struct PlainFaces;
+
+    impl Rules for PlainFaces {
+        fn card_value(c: char) -> u32 {
+            match c {
+                'K' | 'Q' | 'J' => 11,
+                _ => card_to_val(c)
+            }
+        }
+    }
+
+    #[test]
+    fn equal_ranks_are_equal_hands() {
+        let (kings, queens) = (hand::<PlainFaces>("KK234 1"), hand::<PlainFaces>("QQ234 2"));
+        assert_eq!(kings.cmp(&queens), Ordering::Equal);
+        assert!(kings == queens);
+        assert!(hand::<Standard>("KK234 1") != hand::<Standard>("QQ234 1"));
+    }
+
+    #[test]
+    fn standard_and_joker_hand_types() {
+        assert_eq!(Standard::hand_type("T55J5"), HandType::ThreeOfAKind);
+        assert_eq!(JokersWild::hand_type("T55J5"), HandType::FourOfAKind);
+        assert_eq!(JokersWild::hand_type("JJJJJ"), HandType::FiveOfAKind);
+        assert_eq!(JokersWild::hand_type("2345J"), HandType::OnePair);
+        assert_eq!(JokersWild::hand_type("2233J"), HandType::FullHouse);
+    }
+
+    #[test]
+    fn several_wild_cards() {
+        assert_eq!(AcesLowDeucesWild::hand_type("2J345"), HandType::ThreeOfAKind);
+        assert_eq!(AcesLowDeucesWild::hand_type("22JKK"), HandType::FiveOfAKind);
+        assert!(hand::<AcesLowDeucesWild>("K3456 1") > hand::<AcesLowDeucesWild>("A3456 1"));
+        assert!(hand::<Standard>("K3456 1") < hand::<Standard>("A3456 1"));
+    }
+
+    #[test]
+    fn smaller_hands() {
+        assert_eq!(ThreeCardHands::hand_type("KKK"), HandType::ThreeOfAKind);
+        assert_eq!(ThreeCardHands::hand_type("KK2"), HandType::OnePair);
+        assert_eq!(winnings_under::<ThreeCardHands>("KK2 10\nAAA 1\n234 100"), Ok(100 + 20 + 3));
+    }
+
+    #[test]
+    fn rules_choose_the_labels() {
+        assert_eq!(hand::<WithWildcard>("WW234 5").hand_type, HandType::ThreeOfAKind);
+        assert!(hand::<WithWildcard>("W2345 1") < hand::<WithWildcard>("22345 1"));
+        assert!(parse_all("WW234 5", Hand::<Standard>::parse).is_err());
+        assert_eq!(card_to_val('A'), 14);
+        assert_eq!(card_to_val('2'), 2);
+    }
+
+    #[test]
+    fn rejects_malformed_hands() {
+        let error = |input| winnings_under::<ThreeCardHands>(input).unwrap_err().to_string();
+        assert_eq!(error("KKK 1\nKKKKK 1"), "line 2, column 1: expected 3 cards out of AKQJT98765432, found \"KKKKK\"");
+        assert_eq!(error("KXK 1"), "line 1, column 1: expected 3 cards out of AKQJT98765432, found \"KXK\"");
+        assert_eq!(error("KKK"), "line 1, column 4: expected a space, found end of input");
+        assert_eq!(error("KKK bid"), "line 1, column 5: expected a number, found 'b'");
+    }
+}
@@ -0,0 +1,303 @@
+use std::{collections::{HashMap, HashSet}, fmt};
+
+use itertools::Itertools;
+
+use crate::libaoc::{Answer, Part, SolveError, Solution};
+use crate::libaoc::number_theory::crt;
+use crate::libaoc::parse::{lines, Cursor, ParseError};
+use crate::libaoc::sections::{SectionError, Sections};
+
+pub struct Day08;
+
+type Graph = HashMap<String, (String, String)>;
+
+type Instructions<'a> = &'a str;
+
+#[allow(non_snake_case)]
+fn L (choices: &(String, String)) -> &String {
+    &choices.0
+}
+
+#[allow(non_snake_case)]
+fn R(choices: &(String, String)) -> &String {
+    &choices.1
+}
+
+fn parse_node(c: &mut Cursor) -> Result<(String, (String, String)), ParseError> {
+    // AAA = (BBB, CCC)
+    let from = c.word()?;
+    c.literal(" = (")?;
+    let left = c.word()?;
+    c.literal(", ")?;
+    let right = c.word()?;
+    c.literal(")")?;
+    Ok((from, (left, right)))
+}
+
+fn parse_instructions(c: &mut Cursor) -> Result<String, ParseError> {
+    let mut steps = String::new();
+    loop {
+        if c.optional("L") { steps.push('L') } else if c.optional("R") { steps.push('R') } else { break }
+    }
+    if steps.is_empty() { return Err(c.error("'L' or 'R'")) }
+    Ok(steps)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NetworkError {
+    Input(SectionError),
+    /// A node leads to one that is never defined.
+    Undefined { node: String, from: String },
+    /// A node the walk has to start or end at is not in the network.
+    Missing(String),
+    /// The walk from `from` loops forever without reaching `to`.
+    Unreachable { from: String, to: String },
+}
+
+impl From<SectionError> for NetworkError {
+    fn from(e: SectionError) -> NetworkError {
+        NetworkError::Input(e)
+    }
+}
+
+impl From<NetworkError> for SolveError {
+    fn from(e: NetworkError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Input(e) => write!(f, "{}", e),
+            NetworkError::Undefined { node, from } => write!(f, "node {} leads to {}, which is not defined", from, node),
+            NetworkError::Missing(node) => write!(f, "no node {} in the network", node),
+            NetworkError::Unreachable { from, to } => write!(f, "the walk from {} never reaches {}", from, to),
+        }
+    }
+}
+
+/// Reads the instructions and the network. Every node a node leads to must
+/// be defined too, so walking the graph never leaves it.
+fn parse_definition(def: &str) -> Result<(Graph, String), NetworkError> {
+    let mut sections = Sections::read(def);
+    let (instructions, network) = (sections.next("instructions")?, sections.next("network")?);
+    sections.end()?;
+    let nodes = network.parse(|c| lines(c, parse_node))?;
+    let instructions = instructions.parse(parse_instructions)?;
+    let graph = Graph::from_iter(nodes.iter().cloned());
+    for (from, (left, right)) in &nodes {
+        if let Some(node) = [left, right].into_iter().find(|n| !graph.contains_key(*n)) {
+            return Err(NetworkError::Undefined { node: node.clone(), from: from.clone() });
+        }
+    }
+    Ok((graph, instructions))
+}
+
+fn run(graph: &Graph, instructions: Instructions) -> Result<u32, NetworkError> {
+    for node in ["AAA", "ZZZ"] {
+        if !graph.contains_key(node) { return Err(NetworkError::Missing(String::from(node))) }
+    }
+    // As in `GhostCycle::trace`, a repeated (node, instruction) state means
+    // the walk is going round in circles.
+    let mut seen: HashSet<(&str, usize)> = HashSet::new();
+    let mut current = "AAA";
+    let mut count: u32 = 0;
+    for (index, c) in instructions.chars().enumerate().cycle() {
+        if !seen.insert((current, index)) {
+            return Err(NetworkError::Unreachable { from: String::from("AAA"), to: String::from("ZZZ") });
+        }
+        current = step(graph, current, c);
+        count += 1;
+        if current == "ZZZ" { break }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+fn run_parallel_lock_step(graph: &Graph, instructions: Instructions) -> u64 {
+    let mut count : u64 = 0;
+    let mut positions = graph.keys().filter(|p| p.ends_with('A')).collect_vec();
+    for c in instructions.chars().cycle() {
+        positions = positions.iter().map(|p| step(graph, p, c)).collect_vec();
+        count +=1;
+        if positions.iter().all(|p| p.ends_with('Z')) { break }
+
+    }
+    count
+}
+
+/// Where `instruction` leads from `node`. Parsing made sure every node
+/// reached this way is defined.
+fn step<'a>(graph: &'a Graph, node: &str, instruction: char) -> &'a String {
+    let options = &graph[node];
+    match instruction {
+        'L' => L(options),
+        'R' => R(options),
+        _ => panic!("Unexpected instruction '{}'", instruction)
+    }
+}
+
+/// One ghost's walk. A ghost's state is its node plus its position in the
+/// instructions, so after at most `nodes * instructions` steps it repeats a
+/// state and loops forever from there.
+#[derive(Debug, PartialEq, Eq)]
+struct GhostCycle {
+    /// Step at which the loop is entered.
+    start: u64,
+    length: u64,
+    /// Steps before `start` that end on a `Z` node; these happen only once.
+    early_hits: Vec<u64>,
+    /// Steps in `start..start+length` that end on a `Z` node; these recur
+    /// every `length` steps.
+    loop_hits: Vec<u64>,
+}
+
+impl GhostCycle {
+    fn trace(graph: &Graph, instructions: Instructions, from: &str) -> GhostCycle {
+        let instructions = instructions.chars().collect_vec();
+        let mut seen: HashMap<(&str, usize), u64> = HashMap::new();
+        let mut hits = vec![];
+        let mut node = from;
+        let mut steps: u64 = 0;
+        loop {
+            let index = (steps % instructions.len() as u64) as usize;
+            if let Some(&start) = seen.get(&(node, index)) {
+                let (early_hits, loop_hits) = hits.into_iter().partition(|h| *h < start);
+                return GhostCycle { start, length: steps - start, early_hits, loop_hits };
+            }
+            seen.insert((node, index), steps);
+            if steps > 0 && node.ends_with('Z') { hits.push(steps) }
+            node = step(graph, node, instructions[index]);
+            steps += 1;
+        }
+    }
+
+    fn is_hit(&self, t: u64) -> bool {
+        if t < self.start { return self.early_hits.contains(&t) }
+        let phase = self.start + (t - self.start) % self.length;
+        self.loop_hits.contains(&phase)
+    }
+}
+
+/// First step at which every ghost stands on a `Z` node at once, or `None`
+/// if that never happens.
+fn run_parallel(graph: &Graph, instructions: Instructions) -> Option<u64> {
+    let ghosts = graph.keys().filter(|p| p.ends_with('A')).sorted()
+        .map(|start| GhostCycle::trace(graph, instructions, start))
+        .collect_vec();
+    if ghosts.is_empty() { return None }
+
+    // A one-off hit before some ghost settles into its loop.
+    let early = ghosts.iter()
+        .flat_map(|g| g.early_hits.iter())
+        .filter(|t| ghosts.iter().all(|g| g.is_hit(**t)))
+        .min()
+        .copied();
+    if early.is_some() { return early }
+
+    // Otherwise pick one recurring hit per ghost and line them up. Each pick
+    // is a congruence t ≡ hit (mod length) valid from that hit onwards.
+    ghosts.iter()
+        .map(|g| g.loop_hits.iter().map(move |h| (*h, g.length)))
+        .multi_cartesian_product()
+        .filter_map(|picks| {
+            let congruences = picks.iter().map(|(h, len)| (h % len, *len)).collect_vec();
+            let (t, period) = crt(&congruences)?;
+            let earliest = picks.iter().map(|(h, _)| *h).max().unwrap();
+            if t >= earliest { return Some(t) }
+            (earliest - t).div_ceil(period).checked_mul(period)?.checked_add(t)
+        })
+        .min()
+}
+
+impl Solution for Day08 {
+    fn day(&self) -> u32 { 8 }
+
+    fn example_file(&self, part: Part) -> String {
+        match part {
+            Part::One => String::from("files/08-example-1.txt"),
+            Part::Two => String::from("files/08-example-3.txt"),
+        }
+    }
+
+    fn part_one(&self, input: &str) -> Answer {
+        let (graph, instructions) = parse_definition(input)?;
+        Ok(run(&graph, &instructions)?.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        let (graph, instructions) = parse_definition(input)?;
+        run_parallel(&graph, &instructions)
+            .map(|t| t.to_string())
+            .ok_or(SolveError::Failed(String::from("the ghosts never all stand on a Z node at once")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::input::read_input;
+
+    #[test]
+    fn cycle_solver_matches_lock_step_on_example() {
+        let example = read_input("files/08-example-3.txt").unwrap();
+        let (graph, instructions) = parse_definition(&example).unwrap();
+        assert_eq!(run_parallel(&graph, &instructions), Some(run_parallel_lock_step(&graph, &instructions)));
+    }
+
+    #[test]
+    fn traces_each_ghost_cycle() {
+        let example = read_input("files/08-example-3.txt").unwrap();
+        let (graph, instructions) = parse_definition(&example).unwrap();
+        let ghost = GhostCycle::trace(&graph, &instructions, "22A");
+        assert_eq!(ghost, GhostCycle { start: 1, length: 6, early_hits: vec![], loop_hits: vec![3, 6] });
+        assert!(ghost.is_hit(9));
+        assert!(!ghost.is_hit(10));
+    }
+
+    #[test]
+    fn windows_line_endings_and_missing_sections() {
+        let (graph, instructions) = parse_definition("LR  \r\n \r\n\r\nAAA = (ZZZ, AAA)\r\nZZZ = (ZZZ, ZZZ)\r\n").unwrap();
+        assert_eq!((graph.len(), instructions.as_str()), (2, "LR"));
+        assert_eq!(run(&graph, &instructions), Ok(1));
+        assert_eq!(
+            parse_definition("LR\nAAA = (ZZZ, AAA)").unwrap_err().to_string(),
+            "expected at least 2 sections separated by blank lines, found 1");
+        assert_eq!(
+            parse_definition("LR\n\nAAA = (ZZZ, AAA)\nZZZ = ZZZ, ZZZ)").unwrap_err().to_string(),
+            "in network: line 4, column 4: expected \" = (\", found ' '");
+    }
+
+    #[test]
+    fn undefined_nodes_are_errors() {
+        assert_eq!(
+            parse_definition("LR\n\nAAA = (BBB, BBB)").unwrap_err(),
+            NetworkError::Undefined { node: String::from("BBB"), from: String::from("AAA") });
+        let (graph, instructions) = parse_definition("L\n\nAAA = (AAA, AAA)").unwrap();
+        assert_eq!(run(&graph, &instructions).unwrap_err().to_string(), "no node ZZZ in the network");
+        let (graph, instructions) = parse_definition("L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(run(&graph, &instructions).unwrap_err().to_string(), "the walk from AAA never reaches ZZZ");
+        assert_eq!(
+            Day08.part_one("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err().to_string(),
+            "node AAA leads to BBB, which is not defined");
+    }
+
+    #[test]
+    fn offset_cycles_are_combined_with_crt() {
+        // 11A reaches Z at steps 1, 5, 9, ...; 22A at 2, 5, 8, ... so they
+        // first meet at 5, well before the lcm of their loop lengths.
+        let def = "L\n\n11A = (11Z, XXX)\n11Z = (11B, XXX)\n11B = (11C, XXX)\n11C = (11D, XXX)\n11D = (11Z, XXX)\n22A = (22B, XXX)\n22B = (22Z, XXX)\n22Z = (22C, XXX)\n22C = (22B, XXX)\nXXX = (XXX, XXX)";
+        let (graph, instructions) = parse_definition(def).unwrap();
+        assert_eq!(run_parallel(&graph, &instructions), Some(5));
+        assert_eq!(run_parallel_lock_step(&graph, &instructions), 5);
+    }
+
+    #[test]
+    fn ghosts_that_never_align() {
+        // Both loops have length 2, but one hits Z on odd steps and the other on even ones.
+        let def = "L\n\n11A = (11Z, XXX)\n11Z = (11B, XXX)\n11B = (11Z, XXX)\n22A = (22B, XXX)\n22B = (22Z, XXX)\n22Z = (22B, XXX)\nXXX = (XXX, XXX)";
+        let (graph, instructions) = parse_definition(def).unwrap();
+        assert_eq!(run_parallel(&graph, &instructions), None);
+    }
+}
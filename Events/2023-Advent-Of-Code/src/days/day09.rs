@@ -0,0 +1,76 @@
+use crate::libaoc::{Answer, Solution};
+use crate::libaoc::parse::{lines, parse_all, spaced, ParseError};
+
+pub struct Day09;
+
+fn parse_sequences(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    parse_all(input, |c| lines(c, |c| spaced(c, |c| c.number())))
+}
+
+fn seq_to_diff_seq(seq: &[i64]) -> Vec<i64> {
+    seq.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// Sums the last element of each row of differences. Rows shrink by one
+/// each time, so this stops even for sequences that never level off.
+fn next_element(seq: &[i64]) -> i64 {
+    let mut new = seq.to_vec();
+    let mut ds: Vec<i64> = vec![];
+    while let Some(last) = new.last().filter(|_| new.iter().any(|i| *i != 0)) {
+        ds.push(*last);
+        new = seq_to_diff_seq(&new);
+    }
+    ds.iter().sum::<i64>()
+}
+
+fn sum_of_next_elements(seqs: &[Vec<i64>]) -> i64 {
+    seqs.iter().map(|seq| next_element(seq)).sum()
+}
+
+fn previous_element(seq: &[i64]) -> i64 {
+    let mut new = seq.to_vec();
+    let mut firsts: Vec<i64> = vec![];
+    while let Some(first) = new.first().filter(|_| new.iter().any(|i| *i != 0)) {
+        firsts.push(*first);
+        new = seq_to_diff_seq(&new);
+    }
+    firsts.iter().rev().fold(0, |acc, first| first - acc)
+}
+
+fn sum_of_previous_elements(seqs: &[Vec<i64>]) -> i64 {
+    seqs.iter().map(|seq| previous_element(seq)).sum()
+}
+
+impl Solution for Day09 {
+    fn day(&self) -> u32 { 9 }
+
+    fn part_one(&self, input: &str) -> Answer {
+        Ok(sum_of_next_elements(&parse_sequences(input)?).to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        Ok(sum_of_previous_elements(&parse_sequences(input)?).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
+
+    #[test]
+    fn example() {
+        let seqs = parse_sequences(EXAMPLE).unwrap();
+        assert_eq!(sum_of_next_elements(&seqs), 114);
+        assert_eq!(sum_of_previous_elements(&seqs), 2);
+    }
+
+    #[test]
+    fn short_and_malformed_sequences() {
+        assert_eq!((next_element(&[7]), previous_element(&[7])), (7, 7));
+        assert_eq!((next_element(&[]), previous_element(&[])), (0, 0));
+        assert_eq!(parse_sequences("1 2\n\n3 4").unwrap_err().to_string(), "line 3, column 1: expected end of input, found '3'");
+        assert_eq!(parse_sequences("1 2 x").unwrap_err().to_string(), "line 1, column 5: expected end of input, found 'x'");
+    }
+}
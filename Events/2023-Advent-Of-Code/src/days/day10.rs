@@ -0,0 +1,362 @@
+use std::fmt;
+
+use crate::libaoc::{polygon, to_lines, Answer, GridCell, Part, SolveError, Solution};
+use crate::libaoc::coord::{Coord, Direction};
+use crate::libaoc::path::Paths;
+use crate::libaoc::render::{Colour, Render, Style};
+use crate::libaoc::typed::{CellParseError, FromChar, TypedGrid};
+
+pub struct Day10;
+
+/*
+| is a vertical pipe connecting north and south.
+- is a horizontal pipe connecting east and west.
+L is a 90-degree bend connecting north and east.
+J is a 90-degree bend connecting north and west.
+7 is a 90-degree bend connecting south and west.
+F is a 90-degree bend connecting south and east.
+. is ground; there is no pipe in this tile.
+S is the starting position of the animal;
+
+For visualisation, run with --render, which draws the pipes with TR_TABLE.
+*/
+
+const TR_TABLE: &[(char,char)] = &[
+    (Pipe::NorthSouth.symbol(),'┃'),
+    (Pipe::EastWest.symbol(),'━'),
+    (Pipe::NorthEast.symbol(),'┗'),
+    (Pipe::NorthWest.symbol(),'┛'),
+    (Pipe::SouthWest.symbol(),'┓'),
+    (Pipe::SouthEast.symbol(),'┏'),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pipe {
+    NorthSouth,
+    EastWest,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start
+}
+
+impl Pipe {
+    const PIPES: [Pipe; 6] = [Pipe::NorthSouth, Pipe::EastWest, Pipe::NorthEast, Pipe::NorthWest, Pipe::SouthWest, Pipe::SouthEast];
+
+    pub const fn symbol(self) -> char {
+        match self {
+            Pipe::NorthSouth => '|',
+            Pipe::EastWest => '-',
+            Pipe::NorthEast => 'L',
+            Pipe::NorthWest => 'J',
+            Pipe::SouthWest => '7',
+            Pipe::SouthEast => 'F',
+            Pipe::Ground => '.',
+            Pipe::Start => 'S',
+        }
+    }
+
+    /// The two sides a pipe opens onto, or `None` for ground and the start.
+    fn connections(self) -> Option<[Direction; 2]> {
+        match self {
+            Pipe::NorthSouth => Some([Direction::North, Direction::South]),
+            Pipe::EastWest => Some([Direction::East, Direction::West]),
+            Pipe::NorthEast => Some([Direction::North, Direction::East]),
+            Pipe::NorthWest => Some([Direction::North, Direction::West]),
+            Pipe::SouthWest => Some([Direction::South, Direction::West]),
+            Pipe::SouthEast => Some([Direction::South, Direction::East]),
+            Pipe::Ground | Pipe::Start => None
+        }
+    }
+
+    fn between(a: Direction, b: Direction) -> Pipe {
+        Pipe::PIPES.into_iter()
+            .find(|p| p.connections().is_some_and(|c| c.contains(&a) && c.contains(&b)))
+            .unwrap()
+    }
+}
+
+impl FromChar for Pipe {
+    fn from_char(c: char) -> Option<Pipe> {
+        Pipe::PIPES.into_iter().chain([Pipe::Ground, Pipe::Start]).find(|p| p.symbol() == c)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoopError {
+    Parse(CellParseError),
+    NoStart,
+    /// `S` does not have exactly two neighbouring pipes pointing at it.
+    UnclearStart { at: Coord, connected: usize },
+    /// The walk reached a tile that is not a pipe.
+    DeadEnd { at: Coord, value: Pipe },
+    /// The pipe at `at` opens onto the edge of the grid.
+    OffGrid { at: Coord, heading: Direction },
+    /// The walk entered a pipe from a side it does not open onto.
+    Broken { at: Coord, value: Pipe, from: Coord },
+}
+
+impl From<CellParseError> for LoopError {
+    fn from(e: CellParseError) -> LoopError {
+        LoopError::Parse(e)
+    }
+}
+
+impl From<LoopError> for SolveError {
+    fn from(e: LoopError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+impl fmt::Display for LoopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopError::Parse(e) => write!(f, "{}", e),
+            LoopError::NoStart => write!(f, "no 'S' in the grid"),
+            LoopError::UnclearStart { at, connected } =>
+                write!(f, "start {} has {} connecting pipes, expected 2", at, connected),
+            LoopError::DeadEnd { at, value } => write!(f, "dead end at {} ('{}')", at, value.symbol()),
+            LoopError::OffGrid { at, heading } => write!(f, "loop runs off the grid going {:?} from {}", heading, at),
+            LoopError::Broken { at, value, from } =>
+                write!(f, "pipe '{}' at {} does not connect back to {}", value.symbol(), at, from),
+        }
+    }
+}
+
+fn start_pos(grid: &TypedGrid<Pipe>) -> Option<Coord>{
+    grid.find_char(|c| c == Pipe::Start.symbol()).first().map(|m| m.start)
+}
+
+/// Direction of travel from `previous` into the tile next to it.
+fn heading(previous: Coord, current: Coord) -> Direction {
+    previous.direction_to(current).unwrap()
+}
+
+/// The pipe under `S`, inferred from which neighbours point back at it.
+fn start_shape(grid: &TypedGrid<Pipe>, start: Coord) -> Result<Pipe, LoopError> {
+    let open = Direction::CARDINALS.into_iter()
+        .filter(|d| start.step(*d)
+            .and_then(|n| grid.typed(n))
+            .and_then(|p| p.connections())
+            .is_some_and(|c| c.contains(&d.opposite())))
+        .collect::<Vec<Direction>>();
+    if open.len() != 2 {
+        return Err(LoopError::UnclearStart { at: start, connected: open.len() });
+    }
+    Ok(Pipe::between(open[0], open[1]))
+}
+
+fn pipe_at(grid: &TypedGrid<Pipe>, start: Coord, start_pipe: Pipe, at: Coord) -> Option<Pipe> {
+    if at == start { return Some(start_pipe) }
+    grid.typed(at).copied()
+}
+
+/// The tile after `current` when arriving from `previous`. Both are on the
+/// grid; the walk never leaves it.
+fn next(grid: &TypedGrid<Pipe>, start: Coord, start_pipe: Pipe, previous: Coord, current: Coord) -> Result<Coord, LoopError> {
+    let value = pipe_at(grid, start, start_pipe, current).unwrap();
+    let [a, b] = value.connections().ok_or(LoopError::DeadEnd { at: current, value })?;
+    let back = heading(previous, current).opposite();
+    let out = if a == back { b } else if b == back { a } else {
+        return Err(LoopError::Broken { at: current, value, from: previous });
+    };
+    grid.step(current, out).ok_or(LoopError::OffGrid { at: current, heading: out })
+}
+
+/// Every tile of the loop through `S`, in walking order starting at `S`.
+pub fn trace_loop(grid: &TypedGrid<Pipe>) -> Result<Vec<Coord>, LoopError> {
+    let start = start_pos(grid).ok_or(LoopError::NoStart)?;
+    let start_pipe = start_shape(grid, start)?;
+    let first = start.step(start_pipe.connections().unwrap()[0]).unwrap();
+
+    let mut tiles = vec![start];
+    let (mut previous, mut current) = (start, first);
+    while current != start {
+        tiles.push(current);
+        let following = next(grid, start, start_pipe, previous, current)?;
+        (previous, current) = (current, following);
+    }
+    Ok(tiles)
+}
+
+/// Walks away from `S` in both directions at once until the two walkers
+/// meet; the number of steps taken is the distance to the farthest tile.
+fn farthest_distance(grid: &TypedGrid<Pipe>) -> Result<usize, LoopError> {
+    let start = start_pos(grid).ok_or(LoopError::NoStart)?;
+    let start_pipe = start_shape(grid, start)?;
+    let [a, b] = start_pipe.connections().unwrap();
+    let mut walkers = [(start, start.step(a).unwrap()), (start, start.step(b).unwrap())];
+    let mut steps = 1;
+    loop {
+        let [(prev_a, at_a), (prev_b, at_b)] = walkers;
+        // Meeting on a tile, or passing each other between two tiles.
+        if at_a == at_b { return Ok(steps) }
+        if at_a == prev_b && at_b == prev_a { return Ok(steps - 1) }
+        for walker in walkers.iter_mut() {
+            let following = next(grid, start, start_pipe, walker.0, walker.1)?;
+            *walker = (walker.1, following);
+        }
+        steps += 1;
+    }
+}
+
+/// The grid drawn with box-drawing pipes, the loop in yellow and the tiles
+/// it encloses filled with green `I`s.
+fn render_loop(grid: &TypedGrid<Pipe>, style: Style) -> Result<String, LoopError> {
+    let tiles = trace_loop(grid)?;
+    let render = Render::new(TR_TABLE, style)
+        .highlight(tiles.iter().copied(), Colour::Yellow, None)
+        // The loop moves one tile at a time between pipes, so no edge is
+        // diagonal.
+        .highlight(polygon::enclosed_tiles(&tiles).unwrap_or_default(), Colour::Green, Some('I'));
+    Ok(grid.render(&render))
+}
+
+/// Distances along the loop from `S` to every tile on it, by searching the
+/// grid for moves between pipes that open onto each other.
+pub fn loop_distances(grid: &TypedGrid<Pipe>) -> Result<Paths, LoopError> {
+    let start = start_pos(grid).ok_or(LoopError::NoStart)?;
+    let start_pipe = start_shape(grid, start)?;
+    let connected = |from: &GridCell<()>, to: &GridCell<()>| {
+        let (a, b) = (from.coord(), to.coord());
+        let opens = |at: Coord, d: Direction| pipe_at(grid, start, start_pipe, at)
+            .and_then(Pipe::connections)
+            .is_some_and(|c| c.contains(&d));
+        let dir = heading(a, b);
+        opens(a, dir) && opens(b, dir.opposite())
+    };
+    Ok(grid.bfs(start, connected))
+}
+
+fn parse_grid(input: &str) -> Result<TypedGrid<Pipe>, LoopError> {
+    Ok(TypedGrid::parse(&to_lines(input))?)
+}
+
+impl Solution for Day10 {
+    fn day(&self) -> u32 { 10 }
+
+    fn example_file(&self, part: Part) -> String {
+        match part {
+            Part::One => String::from("files/10-example-1.txt"),
+            Part::Two => String::from("files/10-example-3.txt"),
+        }
+    }
+
+    fn part_one(&self, input: &str) -> Answer {
+        Ok(farthest_distance(&parse_grid(input)?)?.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Answer {
+        let tiles = trace_loop(&parse_grid(input)?)?;
+        Ok(polygon::interior_points(&tiles).to_string())
+    }
+
+    fn render(&self, input: &str, style: Style) -> Answer {
+        Ok(render_loop(&parse_grid(input)?, style)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::TextGrid;
+
+    fn grid(s: &str) -> TypedGrid<Pipe> {
+        parse_grid(s).unwrap()
+    }
+
+    fn example(file: &str) -> TypedGrid<Pipe> {
+        TypedGrid::decode(TextGrid::from_file(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn infers_the_pipe_under_s() {
+        let g = example("files/10-example-1.txt");
+        assert_eq!(start_shape(&g, Coord::new(1, 1)), Ok(Pipe::SouthEast));
+        let g = example("files/10-example-2.txt");
+        assert_eq!(start_shape(&g, Coord::new(2, 0)), Ok(Pipe::SouthEast));
+    }
+
+    #[test]
+    fn farthest_point_of_the_examples() {
+        assert_eq!(farthest_distance(&example("files/10-example-1.txt")), Ok(4));
+        assert_eq!(farthest_distance(&example("files/10-example-2.txt")), Ok(8));
+    }
+
+    #[test]
+    fn searching_the_loop_finds_the_same_farthest_point() {
+        for file in ["files/10-example-1.txt", "files/10-example-2.txt", "files/10-input.txt"] {
+            let g = example(file);
+            let paths = loop_distances(&g).unwrap();
+            assert_eq!(paths.distances.len(), trace_loop(&g).unwrap().len(), "{}", file);
+            assert_eq!(paths.farthest().map(|(_, d)| d as usize), farthest_distance(&g).ok(), "{}", file);
+        }
+    }
+
+    #[test]
+    fn loop_is_traced_in_order() {
+        let tiles = trace_loop(&grid(".....\n.S-7.\n.|.|.\n.L-J.\n.....")).unwrap();
+        assert_eq!(tiles, vec![Coord::new(1, 1), Coord::new(2, 1), Coord::new(3, 1), Coord::new(3, 2), Coord::new(3, 3), Coord::new(2, 3), Coord::new(1, 3), Coord::new(1, 2)]);
+    }
+
+    #[test]
+    fn tiles_enclosed_by_the_examples() {
+        for (file, expected) in [("3", 4), ("4", 4), ("5", 8)] {
+            let tiles = trace_loop(&example(&format!("files/10-example-{}.txt", file))).unwrap();
+            assert_eq!(polygon::interior_points(&tiles), expected, "example {}", file);
+            assert_eq!(polygon::interior_points_scanline(&tiles), Some(expected), "example {}", file);
+        }
+    }
+
+    #[test]
+    fn renders_the_loop_and_what_it_encloses() {
+        let g = grid(".....\n.S-7.\n.|.|.\n.L-J.\n.....");
+        assert_eq!(render_loop(&g, Style::Plain), Ok(String::from(".....\n.S━┓.\n.┃I┃.\n.┗━┛.\n.....\n")));
+    }
+
+    #[test]
+    fn odd_loops_meet_between_tiles() {
+        // 6 tiles: the farthest tile is 3 steps away either way.
+        assert_eq!(farthest_distance(&grid("S-7\nL-J")), Ok(3));
+    }
+
+    #[test]
+    fn reports_broken_loops() {
+        assert_eq!(farthest_distance(&grid("....\n.S7.\n.LJ.")), Ok(2));
+        assert_eq!(
+            farthest_distance(&grid(".S7\n.|J\n.L.")).unwrap_err(),
+            LoopError::DeadEnd { at: Coord::new(2, 2), value: Pipe::Ground });
+        assert_eq!(
+            farthest_distance(&grid("S-7\n|.-\nL-J")).unwrap_err(),
+            LoopError::Broken { at: Coord::new(1, 2), value: Pipe::EastWest, from: Coord::new(0, 2) });
+    }
+
+    #[test]
+    fn rejects_unknown_tiles() {
+        let e = parse_grid("S-7\n|?|\nL-J").unwrap_err();
+        assert_eq!(e, LoopError::Parse(CellParseError { line: 1, offset: 1, value: '?', expected: "Pipe" }));
+        assert_eq!(e.to_string(), "unexpected '?' at line 2, column 2 (not a Pipe)");
+    }
+
+    #[test]
+    fn reports_loops_leaving_the_grid_on_every_side() {
+        let off = |at: Coord, heading: Direction| LoopError::OffGrid { at, heading };
+        assert_eq!(trace_loop(&grid("|.\nS7\n..")).unwrap_err(), off(Coord::new(0, 0), Direction::North));
+        assert_eq!(trace_loop(&grid("S7\nL-")).unwrap_err(), off(Coord::new(1, 1), Direction::East));
+        assert_eq!(trace_loop(&grid("S7\n|.")).unwrap_err(), off(Coord::new(1, 0), Direction::South));
+        assert_eq!(farthest_distance(&grid("-S\n.|\n.|")).unwrap_err(), off(Coord::new(0, 0), Direction::West));
+        assert_eq!(
+            trace_loop(&grid("|.\nS7\n..")).unwrap_err().to_string(),
+            "loop runs off the grid going North from (0, 0)");
+    }
+
+    #[test]
+    fn reports_missing_or_unclear_starts() {
+        assert_eq!(farthest_distance(&grid("F7\nLJ")), Err(LoopError::NoStart));
+        assert_eq!(
+            farthest_distance(&grid("...\n.S.\n...")),
+            Err(LoopError::UnclearStart { at: Coord::new(1, 1), connected: 0 }));
+    }
+}
@@ -1 +1,3 @@
-pub mod libaoc;
\ No newline at end of file
+pub mod libaoc;
+pub mod days;
+pub mod answers;
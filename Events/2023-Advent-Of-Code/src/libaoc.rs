@@ -1,22 +1,136 @@
+pub mod coord;
+pub mod find;
+pub mod input;
+pub mod intervals;
+pub mod number_theory;
+pub mod parse;
+pub mod path;
+pub mod polygon;
+pub mod provider;
+pub mod regions;
+pub mod render;
+pub mod sections;
+pub mod sparse;
+pub mod transform;
+pub mod typed;
+pub mod wrapping;
 
-use std::{fs::read_to_string, fmt, rc::Rc};
+use std::{collections::HashSet, fmt, rc::Rc};
 use core::fmt::Debug;
 use std::cmp::Eq;
-use std::hash::Hash;
-use itertools::Itertools;
 
-pub fn read_lines(filename: &str) -> Vec<String> {
-    read_to_string(filename) 
-        .unwrap()  // panic on possible file-reading errors
-        .lines()  // split the string into an iterator of string slices
-        .map(String::from)  // make each slice into a string
-        .collect()  // gather them together into a vector
+use coord::{Coord, Direction};
+use input::{read_input, LoadError};
+use parse::ParseError;
+use sections::SectionError;
+use typed::CellParseError;
+use render::Style;
+
+pub fn read_lines(filename: &str) -> Result<Vec<String>, LoadError> {
+    Ok(to_lines(&read_input(filename)?))
+}
+
+/// Splits an already-loaded puzzle input into owned lines.
+pub fn to_lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Why a part produced no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The part has not been solved yet.
+    NotImplemented,
+    /// The input could not be solved, e.g. because it is malformed.
+    Failed(String)
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NotImplemented => write!(f, "not implemented yet"),
+            SolveError::Failed(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+impl From<SectionError> for SolveError {
+    fn from(e: SectionError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+impl From<CellParseError> for SolveError {
+    fn from(e: CellParseError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+impl From<EntityError> for SolveError {
+    fn from(e: EntityError) -> SolveError {
+        SolveError::Failed(e.to_string())
+    }
+}
+
+pub type Answer = Result<String, SolveError>;
+
+/// A single day's puzzle. Each part receives the full text of its input
+/// and returns its answer, or why there is none.
+pub trait Solution {
+    fn day(&self) -> u32;
+
+    fn part_one(&self, input: &str) -> Answer;
+    fn part_two(&self, input: &str) -> Answer;
+
+    /// Example input used for `part`. Days whose parts use different
+    /// examples override this.
+    fn example_file(&self, _part: Part) -> String {
+        format!("files/{:02}-example.txt", self.day())
+    }
+
+    fn input_file(&self) -> String {
+        format!("files/{:02}-input.txt", self.day())
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Answer {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    /// A picture of the input for days that have something worth drawing.
+    fn render(&self, _input: &str, _style: Style) -> Answer {
+        Err(SolveError::NotImplemented)
+    }
 }
 
 
 pub struct TextGrid<T> {
     pub basis: Vec<String>,
     pub cells: Vec<Vec<GridCell<T>>>,
+    /// Everything placed on the grid, indexed by `EntityId`.
+    pub entities: Vec<Entity<T>>,
 }
 
 #[derive(Clone)]
@@ -24,110 +138,414 @@ pub struct GridCell<T>{
     pub line: usize,
     pub offset: usize,
     pub value: char,
-    pub entity: Option<Rc<T>>
+    pub entity: Option<Rc<T>>,
+    pub entity_id: Option<EntityId>
+}
+
+impl<T> GridCell<T> {
+    pub fn coord(&self) -> Coord {
+        Coord::new(self.line, self.offset)
+    }
 }
 
 impl<T> fmt::Debug for GridCell<T> where T: Debug {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // f.debug_struct("GridCell").field("line", &self.line).field("offset", &self.offset).field("value", &self.value).field("entity", &self.entity).finish()
-        write!(f, "({},{})[{}]: {:?}", &self.line, &self.offset, &self.value, &self.entity)
+        write!(f, "({},{})[{}]: {:?}", &self.line, &self.offset, &self.value, &self.entity)?;
+        if let Some(id) = self.entity_id {
+            write!(f, " #{}", id)?;
+        }
+        Ok(())
     }
 }
 
-fn cells_from_lines<T>(lines: &Vec<String>) -> Vec<Vec<GridCell<T>>>{
+pub type EntityId = usize;
+
+/// A value covering some cells of the grid, usually the rectangle from
+/// `start` to `end` inclusive, such as a run of characters on one line. Two
+/// entities with equal values are still told apart by their `id`.
+#[derive(Debug)]
+pub struct Entity<T> {
+    pub id: EntityId,
+    /// Top-left and bottom-right corners of the cells covered, inclusive.
+    pub start: Coord,
+    pub end: Coord,
+    pub value: Rc<T>,
+    /// The cells covered, when they are not the whole rectangle.
+    shape: Option<HashSet<Coord>>
+}
+
+impl<T> Entity<T> {
+    pub fn contains(&self, at: Coord) -> bool {
+        let in_bounds = (self.start.line..=self.end.line).contains(&at.line) && (self.start.offset..=self.end.offset).contains(&at.offset);
+        in_bounds && self.shape.as_ref().is_none_or(|cells| cells.contains(&at))
+    }
+
+    pub fn cell_count(&self) -> usize {
+        match &self.shape {
+            Some(cells) => cells.len(),
+            None => (self.end.line - self.start.line + 1) * (self.end.offset - self.start.offset + 1)
+        }
+    }
+}
+
+/// Why an entity could not be placed. Cells belong to at most one entity,
+/// so `Entity::contains` and `TextGrid::entity_at` always agree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityError {
+    /// `end` comes before `start` on either axis.
+    Backwards { start: Coord, end: Coord },
+    /// An entity must cover at least one cell.
+    Empty,
+    /// The cell is off the grid or past the end of its line.
+    OutOfBounds(Coord),
+    /// The cell already belongs to entity `id`.
+    Overlaps { at: Coord, id: EntityId },
+}
+
+impl fmt::Display for EntityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityError::Backwards { start, end } => write!(f, "entity ends at {} before it starts at {}", end, start),
+            EntityError::Empty => write!(f, "entity covers no cells"),
+            EntityError::OutOfBounds(at) => write!(f, "entity cell {} is not on the grid", at),
+            EntityError::Overlaps { at, id } => write!(f, "cell {} already belongs to entity #{}", at, id),
+        }
+    }
+}
+
+fn cells_from_lines<T>(lines: &[String]) -> Vec<Vec<GridCell<T>>>{
     let r = lines.iter().enumerate().map(|(line_index, line)| 
             line.chars().enumerate().map(|(char_index, c)| {
                 let gc:GridCell<T> = GridCell::<T> {
                     line: line_index,
                     offset: char_index,
                     value: c,
-                    entity: None
+                    entity: None,
+                    entity_id: None
                 };
-                return gc;
+                gc
             }
             ).collect()
         ).collect();
-    return r;
+    r
 }
 
 impl<T> fmt::Debug for TextGrid<T> where T: Clone + Debug{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("TextGrid").field("basis", &self.basis).field("cells", &self.cells).finish()
+        f.debug_struct("TextGrid").field("basis", &self.basis).field("cells", &self.cells).field("entities", &self.entities).finish()
     }
 }
 
-impl<'a, T> TextGrid <T>
-    where T : Clone + Debug + Eq + Hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Orthogonal neighbours only.
+    Four,
+    /// Diagonal neighbours too.
+    Eight
+}
+
+const NEIGHBOURS4: [Direction; 4] = Direction::CARDINALS;
+/// All eight directions, line by line.
+const NEIGHBOURS8: [Direction; 8] = [
+    Direction::NorthWest, Direction::North, Direction::NorthEast, Direction::West,
+    Direction::East, Direction::SouthWest, Direction::South, Direction::SouthEast
+];
+
+/// Lookups that clip to the grid. Lines may have different lengths, and
+/// coordinates outside the grid are never an error, just absent.
+impl<T> TextGrid<T> {
+    pub fn get_cell(&self, at: Coord) -> Option<&GridCell<T>>{
+        self.cells.get(at.line)?.get(at.offset)
+    }
+
+    /// The cell next to `at` in `dir`, or `None` at the edge of the grid.
+    pub fn step(&self, at: Coord, dir: Direction) -> Option<Coord> {
+        at.step(dir).filter(|next| self.get_cell(*next).is_some())
+    }
+
+    /// The cells north, east, south and west of `center`, in that order.
+    pub fn neighbours4(&self, center: Coord) -> impl Iterator<Item = &GridCell<T>> + '_ {
+        NEIGHBOURS4.into_iter().filter_map(move |d| self.get_cell(center.step(d)?))
+    }
+
+    /// The up to eight cells around `center`, line by line.
+    pub fn neighbours8(&self, center: Coord) -> impl Iterator<Item = &GridCell<T>> + '_ {
+        NEIGHBOURS8.into_iter().filter_map(move |d| self.get_cell(center.step(d)?))
+    }
+
+    pub fn neighbours(&self, center: Coord, connectivity: Connectivity) -> Vec<&GridCell<T>> {
+        match connectivity {
+            Connectivity::Four => self.neighbours4(center).collect(),
+            Connectivity::Eight => self.neighbours8(center).collect(),
+        }
+    }
+
+    /// The square of cells within `radius` of `center` (including it), line
+    /// by line.
+    pub fn window(&self, center: Coord, radius: usize) -> impl Iterator<Item = &GridCell<T>> + '_ {
+        let Coord { line, offset } = center;
+        let last_line = line.saturating_add(radius);
+        (line.saturating_sub(radius)..self.cells.len())
+            .take_while(move |l| *l <= last_line)
+            .flat_map(move |l| {
+                let row = &self.cells[l];
+                let start = offset.saturating_sub(radius).min(row.len());
+                let end = offset.saturating_add(radius).saturating_add(1).min(row.len());
+                row[start..end].iter()
+            })
+    }
+}
+
+impl<T> TextGrid <T>
+    where T : Clone + Debug
 {
-    pub fn from_file(filepath: &str) -> TextGrid<T> {
-       let basis = read_lines(&filepath);
-        TextGrid{
+    pub fn from_file(filepath: &str) -> Result<TextGrid<T>, LoadError> {
+        let basis = read_lines(filepath)?;
+        Ok(TextGrid{
             cells: cells_from_lines(&basis),
-            basis: basis
-        }
+            basis,
+            entities: vec![]
+        })
     }
-    pub fn from_lines(lines: &Vec<String>) -> TextGrid<T> {
+    pub fn from_lines(lines: &[String]) -> TextGrid<T> {
         TextGrid{
             basis: lines.to_vec(),
-            cells: cells_from_lines(lines)
+            cells: cells_from_lines(lines),
+            entities: vec![]
         }
     }
-    pub fn get_entity(&self, line: usize, offset:usize) -> Option<Rc<T>>{
-        if line > self.cells.len()-1 { return None }
-        if offset > self.cells[line].len()-1 { return None }
+    pub fn get_entity(&self, at: Coord) -> Option<Rc<T>>{
+        self.get_cell(at)?.entity.to_owned()
+    }
 
-        let row = &self.cells[line];
-        let cell = &row[offset];
-        return cell.entity.to_owned();
+    /// Places `entity` on the single cell at `at`.
+    pub fn set_entity(&mut self, at: Coord, entity: Rc<T>) -> Result<EntityId, EntityError> {
+        self.add_entity(at, at, entity)
     }
 
-    pub fn set_entity(&mut self, line: usize, offset:usize, entity: Rc<T>){
-        if line > self.cells.len()-1 { panic!("Out of bounds set to TextGrid. After EOF.") }
-        if offset > self.cells[line].len()-1 { panic!("Out of bounds set to TextGrid. After EOL.") }
+    /// Places `value` on every cell from `start` to `end` inclusive. Fails,
+    /// leaving the grid as it was, if any of them is off the grid or already
+    /// belongs to an entity.
+    pub fn add_entity(&mut self, start: Coord, end: Coord, value: Rc<T>) -> Result<EntityId, EntityError> {
+        if start.line > end.line || start.offset > end.offset { return Err(EntityError::Backwards { start, end }) }
+        let cells = (start.line..=end.line)
+            .flat_map(|line| (start.offset..=end.offset).map(move |offset| Coord::new(line, offset)))
+            .collect::<Vec<_>>();
+        self.place(&cells, start, end, None, value)
+    }
 
-        let gc = &mut self.cells[line][offset];
-        gc.entity = Some(entity);
+    /// Places `value` on exactly the given cells, which need not form a
+    /// rectangle. Fails like `add_entity`, or if `cells` is empty.
+    pub fn add_entity_on(&mut self, cells: &[Coord], value: Rc<T>) -> Result<EntityId, EntityError> {
+        if cells.is_empty() { return Err(EntityError::Empty) }
+        let lines = cells.iter().map(|c| c.line);
+        let offsets = cells.iter().map(|c| c.offset);
+        let start = Coord::new(lines.clone().min().unwrap(), offsets.clone().min().unwrap());
+        let end = Coord::new(lines.max().unwrap(), offsets.max().unwrap());
+        self.place(cells, start, end, Some(cells.iter().copied().collect()), value)
+    }
+
+    fn place(&mut self, cells: &[Coord], start: Coord, end: Coord, shape: Option<HashSet<Coord>>, value: Rc<T>) -> Result<EntityId, EntityError> {
+        for at in cells {
+            let gc = self.get_cell(*at).ok_or(EntityError::OutOfBounds(*at))?;
+            if let Some(id) = gc.entity_id { return Err(EntityError::Overlaps { at: *at, id }) }
+        }
+        let id = self.entities.len();
+        for at in cells {
+            let gc = &mut self.cells[at.line][at.offset];
+            gc.entity = Some(value.clone());
+            gc.entity_id = Some(id);
+        }
+        self.entities.push(Entity { id, start, end, value, shape });
+        Ok(id)
+    }
+
+    /// Removes every entity, so ids start from 0 again.
+    pub fn clear_entities(&mut self) {
+        for gc in self.cells.iter_mut().flatten() {
+            gc.entity = None;
+            gc.entity_id = None;
+        }
+        self.entities.clear();
+    }
+
+    pub fn entity(&self, id: EntityId) -> Option<&Entity<T>> {
+        self.entities.get(id)
+    }
+
+    pub fn entity_at(&self, at: Coord) -> Option<&Entity<T>> {
+        self.entity(self.get_cell(at)?.entity_id?)
+    }
+
+    /// The distinct entities on `cells`, in the order they are first seen.
+    pub fn entities_in<'a, I>(&self, cells: I) -> Vec<&Entity<T>>
+        where I: IntoIterator<Item = &'a GridCell<T>>, T: 'a
+    {
+        let mut found: Vec<&Entity<T>> = vec![];
+        for cell in cells {
+            if let Some(e) = cell.entity_id.and_then(|id| self.entity(id)) {
+                if !found.iter().any(|f| f.id == e.id) {
+                    found.push(e);
+                }
+            }
+        }
+        found
     }
 
-    pub fn scan<X>(&self, scanner: X)
+    /// Calls `scanner` on every cell and places whatever it returns on that
+    /// cell as a new entity. Returns the ids of the new entities in order,
+    /// or the first cell that already had an entity.
+    pub fn scan<X>(&mut self, scanner: X) -> Result<Vec<EntityId>, EntityError>
         where X: Fn(&TextGrid<T>, &GridCell<T>) -> Option<T>
-    {   
+    {
+        let mut found = vec![];
         for row in &self.cells {
             for cell in row {
-                let gc = cell;
-                let _entity = scanner(self, &gc);
+                if let Some(entity) = scanner(self, cell) {
+                    found.push((cell.coord(), entity));
+                }
             }
         }
+        found.into_iter()
+            .map(|(at, entity)| self.set_entity(at, Rc::new(entity)))
+            .collect()
     }
 
-    pub fn get_cell(&self, coord: (usize, usize)) -> Option<&GridCell<T>>{
-        let (line,offset) = coord;
-        if line > self.cells.len()-1 { return None }
-        if offset > self.cells[line].len()-1 { return None }
-        Some(&self.cells[line][offset])
-    }
-
-    pub fn get_cells(&self, start:(usize, usize), end: (usize,usize)) -> Vec<&GridCell<T>> {
+    /// Every cell in the rectangle from `start` to `end` inclusive that
+    /// exists; parts outside the grid or past the end of a line are skipped.
+    pub fn get_cells(&self, start: Coord, end: Coord) -> Vec<&GridCell<T>> {
         let mut gcs = vec![];
-        for d in start.0..end.0+1 {
-            for o in start.1..end.1+1 {
-                // println!("Looking up ({},{})", d, o);
-                gcs.push(&self.cells[d][o])
+        for d in start.line..=end.line {
+            for o in start.offset..=end.offset {
+                if let Some(gc) = self.get_cell(Coord::new(d, o)) {
+                    gcs.push(gc)
+                }
             }
         }
-        return gcs; 
+        gcs
     }
 
-    pub fn get_entities(&self, start:(usize, usize), end: (usize,usize)) -> Vec<Rc<T>>{
-        // println!("get_entities from {:?} to {:?}", start, end);
-        let cells = self.get_cells(start, end);
-        let mut rcs = vec![];
-        for cell in cells {
-            if let Some(rc) = &cell.entity{
-                rcs.push(rc.to_owned())
-            }
-        }
-        return rcs.into_iter().unique().collect();
+    /// The distinct entities touching the rectangle from `start` to `end`.
+    pub fn get_entities(&self, start: Coord, end: Coord) -> Vec<&Entity<T>>{
+        self.entities_in(self.get_cells(start, end))
+    }
+
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn grid(s: &str) -> TextGrid<u64> {
+        TextGrid::from_lines(&to_lines(s))
+    }
+
+    fn values<'a>(cells: impl Iterator<Item = &'a GridCell<u64>>) -> String {
+        cells.map(|c| c.value).collect()
+    }
+
+    #[test]
+    fn neighbours_clip_to_the_grid() {
+        let g = grid("abc\ndef\nghi");
+        assert_eq!(values(g.neighbours4(Coord::new(1, 1))), "bfhd");
+        assert_eq!(values(g.neighbours8(Coord::new(1, 1))), "abcdfghi");
+        assert_eq!(values(g.neighbours4(Coord::new(0, 0))), "bd");
+        assert_eq!(values(g.neighbours8(Coord::new(2, 2))), "efh");
+        assert_eq!(values(g.neighbours8(Coord::new(5, 5))), "");
+    }
+
+    #[test]
+    fn stepping_stops_at_the_edge() {
+        let g = grid("abc\nd");
+        assert_eq!(g.step(Coord::new(0, 1), Direction::East), Some(Coord::new(0, 2)));
+        assert_eq!(g.step(Coord::new(0, 2), Direction::East), None);
+        assert_eq!(g.step(Coord::new(0, 1), Direction::SouthWest), Some(Coord::new(1, 0)));
+        assert_eq!(g.step(Coord::new(0, 1), Direction::South), None);
+        assert_eq!(g.step(Coord::new(0, 0), Direction::North), None);
+    }
+
+    #[test]
+    fn ragged_lines_and_empty_grids() {
+        let g = grid("abcd\ne\nfgh");
+        assert_eq!(values(g.neighbours8(Coord::new(1, 0))), "abfg");
+        assert_eq!(values(g.neighbours4(Coord::new(0, 3))), "c");
+        assert_eq!(values(g.window(Coord::new(1, 2), 1)), "bcdgh");
+        assert_eq!(values(g.get_cells(Coord::new(0, 2), Coord::new(2, 3)).into_iter()), "cdh");
+
+        let empty = grid("");
+        assert!(empty.get_cell(Coord::new(0, 0)).is_none());
+        assert!(empty.get_entity(Coord::new(0, 0)).is_none());
+        assert_eq!(values(empty.window(Coord::new(0, 0), 3)), "");
+    }
+
+    #[test]
+    fn equal_entities_keep_their_identity() {
+        let mut g = grid("12*12\n.....");
+        let left = g.add_entity(Coord::new(0, 0), Coord::new(0, 1), Rc::new(12)).unwrap();
+        let right = g.add_entity(Coord::new(0, 3), Coord::new(0, 4), Rc::new(12)).unwrap();
+        assert_ne!(left, right);
+
+        let around = g.entities_in(g.window(Coord::new(0, 2), 1));
+        assert_eq!(around.iter().map(|e| (e.id, *e.value)).collect_vec(), vec![(left, 12), (right, 12)]);
+        assert_eq!(g.entity_at(Coord::new(0, 4)).map(|e| (e.start, e.end, e.cell_count())), Some((Coord::new(0, 3), Coord::new(0, 4), 2)));
+        assert_eq!(g.get_entities(Coord::new(0, 0), Coord::new(1, 4)).len(), 2);
+        assert!(g.entity_at(Coord::new(0, 2)).is_none());
+        assert_eq!(g.get_entity(Coord::new(0, 1)), Some(Rc::new(12)));
+    }
+
+    #[test]
+    fn overlapping_entities_are_rejected() {
+        let mut g = grid("abc");
+        let wide = g.add_entity(Coord::new(0, 0), Coord::new(0, 2), Rc::new(1)).unwrap();
+        assert_eq!(g.set_entity(Coord::new(0, 1), Rc::new(2)), Err(EntityError::Overlaps { at: Coord::new(0, 1), id: wide }));
+        assert_eq!(g.entity_at(Coord::new(0, 1)).map(|e| e.id), Some(wide));
+        assert_eq!(g.entities.len(), 1);
+
+        g.clear_entities();
+        assert!(g.entity_at(Coord::new(0, 0)).is_none());
+        assert_eq!(g.set_entity(Coord::new(0, 1), Rc::new(2)), Ok(0));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn entities_must_fit_on_the_grid() {
+        let mut g = grid("abc
+d");
+        assert_eq!(g.add_entity(Coord::new(0, 1), Coord::new(0, 3), Rc::new(1)), Err(EntityError::OutOfBounds(Coord::new(0, 3))));
+        assert_eq!(g.add_entity(Coord::new(0, 0), Coord::new(1, 1), Rc::new(1)), Err(EntityError::OutOfBounds(Coord::new(1, 1))));
+        assert_eq!(g.add_entity(Coord::new(0, 2), Coord::new(0, 1), Rc::new(1)), Err(EntityError::Backwards { start: Coord::new(0, 2), end: Coord::new(0, 1) }));
+        assert_eq!(g.add_entity_on(&[], Rc::new(1)), Err(EntityError::Empty));
+        assert!(g.entities.is_empty() && g.cells.iter().flatten().all(|gc| gc.entity_id.is_none()));
+    }
+
+    #[test]
+    fn entities_of_any_shape() {
+        let mut g = grid("abc
+def");
+        let corner = g.add_entity_on(&[Coord::new(0, 0), Coord::new(1, 0), Coord::new(1, 1)], Rc::new(1)).unwrap();
+        let other = g.set_entity(Coord::new(0, 1), Rc::new(2)).unwrap();
+        let e = g.entity(corner).unwrap();
+        assert_eq!((e.start, e.end, e.cell_count()), (Coord::new(0, 0), Coord::new(1, 1), 3));
+        assert!(e.contains(Coord::new(1, 1)) && !e.contains(Coord::new(0, 1)));
+        assert_eq!(g.entity_at(Coord::new(0, 1)).map(|e| e.id), Some(other));
+    }
+
+    #[test]
+    fn scan_registers_what_the_scanner_returns() {
+        let mut g = grid("a1\n2b");
+        let ids = g.scan(|_, cell| cell.value.to_digit(10).map(u64::from)).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(g.get_entity(Coord::new(0, 1)), Some(Rc::new(1)));
+        assert_eq!(g.entity_at(Coord::new(1, 0)).map(|e| e.id), Some(ids[1]));
+        assert!(g.get_entity(Coord::new(0, 0)).is_none());
+        assert_eq!(g.scan(|_, cell| (cell.value == '2').then_some(9)), Err(EntityError::Overlaps { at: Coord::new(1, 0), id: ids[1] }));
+    }
+
+    #[test]
+    fn windows_of_any_radius() {
+        let g = grid("abc\ndef\nghi");
+        assert_eq!(values(g.window(Coord::new(1, 1), 0)), "e");
+        assert_eq!(values(g.window(Coord::new(0, 0), 1)), "abde");
+        assert_eq!(values(g.window(Coord::new(1, 1), usize::MAX)), "abcdefghi");
+    }
+}
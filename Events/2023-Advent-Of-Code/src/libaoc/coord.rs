@@ -0,0 +1,162 @@
+//! Positions on a `TextGrid` and the directions between them. Lines grow
+//! downwards, so north is towards line 0 and west towards offset 0.
+
+use std::fmt;
+
+/// A cell position. Ordered line by line, then by offset, which is the
+/// order a grid is read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Coord {
+    pub line: usize,
+    pub offset: usize
+}
+
+impl Coord {
+    pub const fn new(line: usize, offset: usize) -> Coord {
+        Coord { line, offset }
+    }
+
+    /// The neighbouring position in `dir`, or `None` past line or offset 0.
+    /// Whether it is on a particular grid is up to `TextGrid::step`.
+    pub fn step(self, dir: Direction) -> Option<Coord> {
+        self.shifted(dir.delta())
+    }
+
+    pub(crate) fn shifted(self, (dl, doff): (isize, isize)) -> Option<Coord> {
+        Some(Coord::new(self.line.checked_add_signed(dl)?, self.offset.checked_add_signed(doff)?))
+    }
+
+    /// The direction of a neighbouring position, or `None` if `to` is not
+    /// one of the eight cells around this one.
+    pub fn direction_to(self, to: Coord) -> Option<Direction> {
+        let delta = (to.line as isize - self.line as isize, to.offset as isize - self.offset as isize);
+        Direction::ALL.into_iter().find(|d| d.delta() == delta)
+    }
+
+    /// Steps needed moving only orthogonally.
+    pub fn manhattan(self, other: Coord) -> usize {
+        self.line.abs_diff(other.line) + self.offset.abs_diff(other.offset)
+    }
+
+    /// Steps needed when diagonal moves are allowed too.
+    pub fn chebyshev(self, other: Coord) -> usize {
+        self.line.abs_diff(other.line).max(self.offset.abs_diff(other.offset))
+    }
+}
+
+/// `(line, offset)`, like a `Coord` but allowed to be negative, for grids
+/// that reach past line or offset 0.
+pub type Point = (i64, i64);
+
+/// The neighbouring point in `dir`. Unlike `Coord::step` this never fails.
+pub fn shift((line, offset): Point, dir: Direction) -> Point {
+    let (dl, doff) = dir.delta();
+    (line + dl as i64, offset + doff as i64)
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.line, self.offset)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest
+}
+
+impl Direction {
+    /// All eight, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North, Direction::NorthEast, Direction::East, Direction::SouthEast,
+        Direction::South, Direction::SouthWest, Direction::West, Direction::NorthWest
+    ];
+
+    /// North, east, south and west.
+    pub const CARDINALS: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// The change in `(line, offset)` for one step.
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let (dl, doff) = self.delta();
+        dl != 0 && doff != 0
+    }
+
+    /// Turned clockwise by `eighths` of a full turn; negative turns go
+    /// anticlockwise.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        let at = Direction::ALL.iter().position(|d| *d == self).unwrap() as i32;
+        Direction::ALL[(at + eighths).rem_euclid(8) as usize]
+    }
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_and_opposites() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+        assert_eq!(Direction::West.rotate(3), Direction::NorthEast);
+        assert_eq!(Direction::East.rotate(-9), Direction::NorthEast);
+        assert!(Direction::ALL.iter().all(|d| d.turn_right().turn_right() == d.opposite()));
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+    }
+
+    #[test]
+    fn stepping_stops_at_zero() {
+        let c = Coord::new(0, 3);
+        assert_eq!(c.step(Direction::SouthWest), Some(Coord::new(1, 2)));
+        assert_eq!(c.step(Direction::North), None);
+        assert_eq!(Coord::new(2, 0).step(Direction::West), None);
+        assert_eq!(c.direction_to(Coord::new(0, 4)), Some(Direction::East));
+        assert_eq!(c.direction_to(Coord::new(1, 2)), Some(Direction::SouthWest));
+        assert_eq!(c.direction_to(Coord::new(0, 5)), None);
+        assert_eq!(c.direction_to(c), None);
+        assert_eq!(shift((0, 0), Direction::NorthWest), (-1, -1));
+    }
+
+    #[test]
+    fn distances_and_order() {
+        let (a, b) = (Coord::new(1, 7), Coord::new(4, 2));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (8, 5));
+        assert_eq!(a.manhattan(a), 0);
+        assert!(Coord::new(0, 9) < Coord::new(1, 0));
+        assert_eq!(b.to_string(), "(4, 2)");
+    }
+}
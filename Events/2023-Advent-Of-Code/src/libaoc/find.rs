@@ -0,0 +1,104 @@
+//! Searching a `TextGrid` for patterns and getting back grid coordinates
+//! rather than byte offsets into `basis`.
+
+use std::{fmt::Debug, rc::Rc};
+
+use regex::Regex;
+
+use super::{Coord, EntityError, EntityId, TextGrid};
+
+/// A run of cells on one line, from `start` to `end` inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridMatch {
+    pub start: Coord,
+    pub end: Coord,
+    pub text: String
+}
+
+impl<T> TextGrid<T>
+    where T: Clone + Debug
+{
+    /// Every non-empty match of `re` on each line, top to bottom.
+    pub fn find_all(&self, re: &Regex) -> Vec<GridMatch> {
+        let mut found = vec![];
+        for (line, text) in self.basis.iter().enumerate() {
+            for m in re.find_iter(text).filter(|m| !m.is_empty()) {
+                // Regex works in bytes, cells are chars.
+                let start = text[..m.start()].chars().count();
+                let len = m.as_str().chars().count();
+                found.push(GridMatch {
+                    start: Coord::new(line, start),
+                    end: Coord::new(line, start + len - 1),
+                    text: String::from(m.as_str())
+                });
+            }
+        }
+        found
+    }
+
+    /// Every single cell whose character satisfies `pred`.
+    pub fn find_char<P>(&self, pred: P) -> Vec<GridMatch>
+        where P: Fn(char) -> bool
+    {
+        self.cells.iter().flatten()
+            .filter(|gc| pred(gc.value))
+            .map(|gc| GridMatch {
+                start: gc.coord(),
+                end: gc.coord(),
+                text: gc.value.to_string()
+            })
+            .collect()
+    }
+
+    /// Turns each match into an entity covering its cells, skipping those
+    /// for which `make` returns `None`. Stops at the first match whose cells
+    /// already belong to an entity.
+    pub fn attach_all<F>(&mut self, matches: &[GridMatch], mut make: F) -> Result<Vec<EntityId>, EntityError>
+        where F: FnMut(&TextGrid<T>, &GridMatch) -> Option<T>
+    {
+        let mut ids = vec![];
+        for m in matches {
+            if let Some(value) = make(self, m) {
+                ids.push(self.add_entity(m.start, m.end, Rc::new(value))?);
+            }
+        }
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    fn grid(s: &str) -> TextGrid<u32> {
+        TextGrid::from_lines(&to_lines(s))
+    }
+
+    #[test]
+    fn matches_are_in_cell_coordinates() {
+        let g = grid("467..114\n..é*35");
+        let numbers = g.find_all(&Regex::new("[0-9]+").unwrap());
+        let spans = numbers.iter().map(|m| (m.start, m.end, m.text.as_str())).collect::<Vec<_>>();
+        assert_eq!(spans, vec![(Coord::new(0, 0), Coord::new(0, 2), "467"), (Coord::new(0, 5), Coord::new(0, 7), "114"), (Coord::new(1, 4), Coord::new(1, 5), "35")]);
+        assert!(g.find_all(&Regex::new("x*").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn single_characters() {
+        let g = grid("a*\n*b");
+        let stars = g.find_char(|c| c == '*').into_iter().map(|m| m.start).collect::<Vec<_>>();
+        assert_eq!(stars, vec![Coord::new(0, 1), Coord::new(1, 0)]);
+    }
+
+    #[test]
+    fn attaching_entities_to_matches() {
+        let mut g = grid("12.34\n.5...");
+        let numbers = g.find_all(&Regex::new("[0-9]+").unwrap());
+        let ids = g.attach_all(&numbers, |_, m| m.text.parse().ok().filter(|n| n % 2 == 0)).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(g.get_entity(Coord::new(0, 1)), Some(Rc::new(12)));
+        assert_eq!(g.entity_at(Coord::new(0, 4)).map(|e| e.start), Some(Coord::new(0, 3)));
+        assert!(g.entity_at(Coord::new(1, 1)).is_none());
+    }
+}
@@ -0,0 +1,98 @@
+//! Loading puzzle inputs without panicking. Paths under `files/` are found
+//! relative to the crate, so binaries and tests work from any directory, and
+//! `-` reads standard input instead of a file.
+
+use std::{fmt, fs, io::{self, Read}, path::{Path, PathBuf}};
+
+pub const STDIN: &str = "-";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadError {
+    /// The path as it was asked for, before resolving.
+    pub path: String,
+    pub resolved: PathBuf,
+    pub day: Option<u32>,
+    pub kind: io::ErrorKind,
+    pub message: String
+}
+
+impl LoadError {
+    fn new(path: &str, day: Option<u32>, e: io::Error) -> LoadError {
+        LoadError {
+            path: String::from(path),
+            resolved: resolve(path),
+            day,
+            kind: e.kind(),
+            message: e.to_string()
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path == STDIN {
+            write!(f, "could not read standard input")?;
+        } else {
+            write!(f, "could not read {}", self.path)?;
+        }
+        if let Some(day) = self.day {
+            write!(f, " for day {}", day)?;
+        }
+        if self.path != STDIN && Path::new(&self.path) != self.resolved {
+            write!(f, " (looked in {})", self.resolved.display())?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Where `path` is read from: relative paths starting with `files/` are
+/// taken from the crate directory, anything else is left alone.
+pub fn resolve(path: &str) -> PathBuf {
+    let p = Path::new(path);
+    if p.is_relative() && p.starts_with("files") {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join(p)
+    }
+    p.to_path_buf()
+}
+
+fn read(path: &str) -> io::Result<String> {
+    if path == STDIN {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input)
+    }
+    fs::read_to_string(resolve(path))
+}
+
+pub fn read_input(path: &str) -> Result<String, LoadError> {
+    read(path).map_err(|e| LoadError::new(path, None, e))
+}
+
+/// Like `read_input`, but errors also name the day the input was for.
+pub fn read_day_input(day: u32, path: &str) -> Result<String, LoadError> {
+    read(path).map_err(|e| LoadError::new(path, Some(day), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_are_found_from_the_crate_directory() {
+        assert!(resolve("files/01-example.txt").is_absolute());
+        assert_eq!(resolve("/tmp/x.txt"), PathBuf::from("/tmp/x.txt"));
+        assert_eq!(resolve("other/x.txt"), PathBuf::from("other/x.txt"));
+        assert!(read_input("files/01-example.txt").unwrap().starts_with("1abc2"));
+    }
+
+    #[test]
+    fn errors_name_the_path_and_day() {
+        let e = read_day_input(42, "files/42-input.txt").unwrap_err();
+        assert_eq!(e.kind, io::ErrorKind::NotFound);
+        assert_eq!(e.day, Some(42));
+        assert!(e.to_string().starts_with("could not read files/42-input.txt for day 42 (looked in /"), "{}", e);
+
+        let e = read_input("no/such/file.txt").unwrap_err();
+        assert!(e.to_string().starts_with("could not read no/such/file.txt: "), "{}", e);
+    }
+}
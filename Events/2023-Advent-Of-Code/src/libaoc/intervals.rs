@@ -0,0 +1,168 @@
+use std::ops::Range;
+
+/// A set of `u64` values stored as sorted, disjoint, non-adjacent
+/// half-open ranges. Empty ranges are dropped on insertion.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<Range<u64>>
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet { ranges: vec![] }
+    }
+
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set (not the number of ranges).
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        i < self.ranges.len() && self.ranges[i].start <= value
+    }
+
+    pub fn insert(&mut self, range: Range<u64>) {
+        if range.is_empty() { return }
+        // Everything strictly before `range` (and not touching it) stays put,
+        // as does everything strictly after it; the rest is merged.
+        let lo = self.ranges.partition_point(|r| r.end < range.start);
+        let hi = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if lo < hi {
+            merged.start = merged.start.min(self.ranges[lo].start);
+            merged.end = merged.end.max(self.ranges[hi-1].end);
+        }
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = self.clone();
+        for r in &other.ranges {
+            out.insert(r.clone());
+        }
+        out
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = IntervalSet::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                out.ranges.push(start..end);
+            }
+            if a.end < b.end { i += 1 } else { j += 1 }
+        }
+        out
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = IntervalSet::new();
+        let mut j = 0;
+        for r in &self.ranges {
+            let mut start = r.start;
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < r.end {
+                let cut = &other.ranges[k];
+                if cut.start > start {
+                    out.ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+                k += 1;
+            }
+            if start < r.end {
+                out.ranges.push(start..r.end);
+            }
+        }
+        out
+    }
+}
+
+impl From<Range<u64>> for IntervalSet {
+    fn from(range: Range<u64>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl FromIterator<Range<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<u64>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for r in iter {
+            set.insert(r);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent_ranges() {
+        let set: IntervalSet = vec![10..20, 0..5, 5..7, 15..25, 30..30].into_iter().collect();
+        assert_eq!(set.ranges(), &[0..7, 10..25]);
+        assert_eq!(set.len(), 22);
+        assert_eq!(set.min(), Some(0));
+        assert_eq!(set.max(), Some(24));
+    }
+
+    #[test]
+    fn insert_bridges_several_ranges() {
+        let mut set: IntervalSet = vec![0..2, 4..6, 8..10].into_iter().collect();
+        set.insert(1..9);
+        assert_eq!(set, IntervalSet::from(0..10));
+    }
+
+    #[test]
+    fn contains_respects_half_open_ends() {
+        let set: IntervalSet = vec![3..5, 8..9].into_iter().collect();
+        assert!(!set.contains(2));
+        assert!(set.contains(3));
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert!(set.contains(8));
+        assert!(!set.contains(9));
+    }
+
+    #[test]
+    fn intersection_and_difference_split_at_boundaries() {
+        let a: IntervalSet = vec![0..10, 20..30].into_iter().collect();
+        let b = IntervalSet::from(5..25);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(b.difference(&a), IntervalSet::from(10..20));
+        assert_eq!(a.union(&b), IntervalSet::from(0..30));
+    }
+
+    #[test]
+    fn difference_with_many_cuts_inside_one_range() {
+        let a = IntervalSet::from(0..100);
+        let b: IntervalSet = vec![10..20, 30..40, 90..110].into_iter().collect();
+        assert_eq!(a.difference(&b).ranges(), &[0..10, 20..30, 40..90]);
+    }
+}
@@ -0,0 +1,103 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Least common multiple; `lcm(0, x)` is 0. `None` if it does not fit in
+/// a u64.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 { return Some(0) }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub fn lcm_all<I: IntoIterator<Item = u64>>(xs: I) -> Option<u64> {
+    xs.into_iter().try_fold(1, lcm)
+}
+
+/// Returns `(g, x, y)` such that `a*x + b*y == g == gcd(a, b)`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solves the system `t ≡ residue (mod modulus)` for every pair, using the
+/// Chinese Remainder Theorem. Moduli do not need to be coprime. Returns the
+/// smallest non-negative solution and the period after which it repeats
+/// (the lcm of the moduli), or `None` when the congruences contradict, a
+/// modulus is 0 or the period does not fit in a u64.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut r: i128 = 0;
+    let mut m: i128 = 1;
+    for &(residue, modulus) in congruences {
+        if modulus == 0 { return None }
+        let (a, n) = (residue as i128, modulus as i128);
+        let (g, p, _) = extended_gcd(m, n);
+        if (a - r) % g != 0 { return None }
+        // r + m*k ≡ a (mod n)  =>  k ≡ (a-r)/g * p (mod n/g)
+        let step = n / g;
+        let k = ((a - r) / g).rem_euclid(step).checked_mul(p.rem_euclid(step))? % step;
+        m = m.checked_mul(step).filter(|m| *m <= u64::MAX as i128)?;
+        r = (r + m / step * k).rem_euclid(m);
+    }
+    Some((r as u64, m as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+    }
+
+    #[test]
+    fn overflow_gives_none() {
+        assert_eq!(lcm(1 << 40, (1 << 40) - 1), None);
+        assert_eq!(lcm_all([1 << 40, 3, (1 << 40) - 1]), None);
+        assert_eq!(crt(&[(1, 1 << 40), (2, (1 << 40) - 1)]), None);
+        let big = u64::MAX - 58; // prime
+        assert_eq!(crt(&[(big - 1, big), (1, 2)]), None);
+        assert_eq!(crt(&[(big - 1, big), (0, 1)]), Some((big - 1, big)));
+        assert_eq!(crt(&[(3, 1 << 32), (5, (1 << 32) - 1)]).map(|(t, _)| (t % (1 << 32), t % ((1 << 32) - 1))), Some((3, 5)));
+    }
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        for (a, b) in [(240, 46), (17, 5), (5, 17), (12, 18)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a as u64, b as u64) as i128);
+            assert_eq!(a*x + b*y, g);
+        }
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn crt_with_shared_factors() {
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+    }
+
+    #[test]
+    fn crt_rejects_a_zero_modulus() {
+        assert_eq!(crt(&[(0, 0)]), None);
+        assert_eq!(crt(&[(2, 3), (1, 0)]), None);
+    }
+
+    #[test]
+    fn crt_of_nothing_is_everything() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
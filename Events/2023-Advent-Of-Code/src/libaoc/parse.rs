@@ -0,0 +1,275 @@
+//! Small parsers for puzzle inputs. A `Cursor` walks the input and every
+//! step either consumes what it expects or fails with a `ParseError` that
+//! says where, so a malformed line is reported instead of panicking on an
+//! index or an `unwrap`.
+//!
+//! The combinators take parsers as plain closures over a `Cursor`, e.g.
+//! `separated(c, "; ", turn)` for `1 red, 2 blue; 3 green`.
+
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counted from 1, as in an editor.
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, found {}", self.line, self.column, self.expected, self.found)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    /// Byte offset of the next character to read.
+    pos: usize
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Cursor<'a> {
+        Cursor { text, pos: 0 }
+    }
+
+    /// Everything not yet consumed.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Line and column of the next character, both from 1.
+    pub fn position(&self) -> (usize, usize) {
+        let before = &self.text[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+    }
+
+    /// An error at the current position, describing what comes next.
+    pub fn error(&self, expected: &str) -> ParseError {
+        let (line, column) = self.position();
+        let found = match self.peek() {
+            None => String::from("end of input"),
+            Some('\n') | Some('\r') => String::from("end of line"),
+            Some(c) => format!("{:?}", c),
+        };
+        ParseError { line, column, expected: String::from(expected), found }
+    }
+
+    fn take_while<P: Fn(char) -> bool>(&mut self, pred: P) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    pub fn literal(&mut self, expected: &str) -> Result<(), ParseError> {
+        if !self.rest().starts_with(expected) {
+            return Err(self.error(&format!("{:?}", expected)));
+        }
+        self.pos += expected.len();
+        Ok(())
+    }
+
+    /// Consumes `expected` if it comes next, without failing otherwise.
+    pub fn optional(&mut self, expected: &str) -> bool {
+        self.literal(expected).is_ok()
+    }
+
+    /// Skips spaces and tabs, never newlines.
+    pub fn spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Spaces and tabs, at least one.
+    pub fn gap(&mut self) -> Result<(), ParseError> {
+        if self.take_while(|c| c == ' ' || c == '\t').is_empty() {
+            return Err(self.error("a space"));
+        }
+        Ok(())
+    }
+
+    /// A line break, `\n` or `\r\n`, after any trailing spaces.
+    pub fn newline(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        self.spaces();
+        if self.optional("\n") || self.optional("\r\n") { return Ok(()) }
+        self.pos = start;
+        Err(self.error("end of line"))
+    }
+
+    /// A run of ASCII digits with an optional leading `-`, as a `N`.
+    pub fn number<N: FromStr>(&mut self) -> Result<N, ParseError> {
+        let start = *self;
+        let sign = if self.optional("-") { "-" } else { "" };
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            *self = start;
+            return Err(self.error("a number"));
+        }
+        format!("{}{}", sign, digits).parse().map_err(|_| {
+            let mut e = start.error("a number that fits");
+            e.found = format!("{}{}", sign, digits);
+            e
+        })
+    }
+
+    /// A run of letters, digits and underscores.
+    pub fn word(&mut self) -> Result<String, ParseError> {
+        let w = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        if w.is_empty() { return Err(self.error("a word")) }
+        Ok(String::from(w))
+    }
+
+    /// The rest of the current line, without its line break.
+    pub fn line(&mut self) -> &'a str {
+        self.take_while(|c| c != '\n').trim_end_matches('\r')
+    }
+}
+
+/// One or more `item`s with `sep` between them. Every `sep` must be
+/// followed by another item; if that item is malformed, its error is
+/// returned.
+pub fn separated<'a, T, F>(c: &mut Cursor<'a>, sep: &str, item: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&mut Cursor<'a>) -> Result<T, ParseError>
+{
+    let mut items = vec![item(c)?];
+    while c.optional(sep) {
+        items.push(item(c)?);
+    }
+    Ok(items)
+}
+
+/// One or more `item`s on the same line, separated by any amount of
+/// spaces.
+pub fn spaced<'a, T, F>(c: &mut Cursor<'a>, item: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&mut Cursor<'a>) -> Result<T, ParseError>
+{
+    let mut items = vec![item(c)?];
+    loop {
+        let before = *c;
+        if c.gap().is_err() { break }
+        match item(c) {
+            Ok(i) => items.push(i),
+            Err(_) => {
+                *c = before;
+                break
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// `key`, a colon and `value`, with spaces allowed around the colon, as in
+/// `Time:      7  15   30`.
+pub fn key_value<'a, T, F>(c: &mut Cursor<'a>, key: &str, value: F) -> Result<T, ParseError>
+    where F: FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>
+{
+    c.literal(key)?;
+    c.spaces();
+    c.literal(":")?;
+    c.spaces();
+    value(c)
+}
+
+/// One `item` per line, up to a blank line or the end of the input. The
+/// line break after the last item is left alone.
+pub fn lines<'a, T, F>(c: &mut Cursor<'a>, item: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&mut Cursor<'a>) -> Result<T, ParseError>
+{
+    let mut items = vec![item(c)?];
+    loop {
+        let before = *c;
+        if c.newline().is_err() { break }
+        c.spaces();
+        if c.at_end() || c.peek() == Some('\n') || c.peek() == Some('\r') {
+            *c = before;
+            break
+        }
+        items.push(item(c)?);
+    }
+    Ok(items)
+}
+
+/// Runs `parser` over the whole of `text`. Trailing spaces and blank lines
+/// are fine; anything else left over is an error.
+pub fn parse_all<'a, T, F>(text: &'a str, parser: F) -> Result<T, ParseError>
+    where F: FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>
+{
+    let mut c = Cursor::new(text);
+    let value = parser(&mut c)?;
+    while c.newline().is_ok() {}
+    c.spaces();
+    if !c.at_end() { return Err(c.error("end of input")) }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(c: &mut Cursor) -> Result<Vec<u32>, ParseError> {
+        spaced(c, |c| c.number())
+    }
+
+    #[test]
+    fn numbers_words_and_lists() {
+        let game = parse_all("Game 12: 3 blue, -4 red; 1 green\n", |c| {
+            c.literal("Game ")?;
+            let id: u32 = c.number()?;
+            c.literal(": ")?;
+            let turns = separated(c, "; ", |c| separated(c, ", ", |c| {
+                let n: i32 = c.number()?;
+                c.gap()?;
+                Ok((n, c.word()?))
+            }))?;
+            Ok((id, turns))
+        }).unwrap();
+        assert_eq!(game.0, 12);
+        assert_eq!(game.1, vec![vec![(3, String::from("blue")), (-4, String::from("red"))], vec![(1, String::from("green"))]]);
+
+        let e = parse_all("1, 2, x", |c| separated(c, ", ", |c| c.number::<u32>())).unwrap_err();
+        assert_eq!(e.to_string(), "line 1, column 7: expected a number, found 'x'");
+    }
+
+    #[test]
+    fn errors_say_where_and_what() {
+        let e = parse_all("Time: 7 15\nDistance: 9 x", |c| {
+            let time = key_value(c, "Time", numbers)?;
+            c.newline()?;
+            let distance = key_value(c, "Distance", numbers)?;
+            Ok((time, distance))
+        }).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 13: expected end of input, found 'x'");
+
+        let e = parse_all("Card 1 41 48", |c| key_value(c, "Card 1", numbers)).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str(), e.found.as_str()), (1, 8, "\":\"", "'4'"));
+        let e = parse_all("99999999999", |c| c.number::<u32>()).unwrap_err();
+        assert_eq!(e.to_string(), "line 1, column 1: expected a number that fits, found 99999999999");
+        let e = parse_all("a\n", |c| { c.word()?; c.newline()?; c.word() }).unwrap_err();
+        assert_eq!(e.found, "end of input");
+    }
+
+    #[test]
+    fn lines_stop_at_a_blank_line() {
+        let text = "1 2  \r\n3 4\r\n\r\n5 6\r\n";
+        let (first, second) = parse_all(text, |c| {
+            let first = lines(c, numbers)?;
+            c.newline()?;
+            c.newline()?;
+            Ok((first, lines(c, numbers)?))
+        }).unwrap();
+        assert_eq!(first, vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(second, vec![vec![5, 6]]);
+    }
+}
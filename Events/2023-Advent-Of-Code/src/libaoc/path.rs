@@ -0,0 +1,268 @@
+//! Shortest paths over a `TextGrid`, moving between orthogonal neighbours.
+//! Which moves are allowed, and what they cost, is up to the caller: each
+//! search is given the cell being left and the cell being entered.
+
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, VecDeque}, hash::Hash};
+
+use super::{Coord, GridCell, TextGrid};
+
+/// Everything reached by a search, with how far away it is and how to get
+/// there. Keyed by `Coord` on a `TextGrid`, or by whatever position type the
+/// search was run over.
+#[derive(Debug, Clone)]
+pub struct Paths<K = Coord> {
+    pub distances: HashMap<K, u64>,
+    previous: HashMap<K, K>
+}
+
+impl<K> Default for Paths<K> {
+    fn default() -> Paths<K> {
+        Paths { distances: HashMap::new(), previous: HashMap::new() }
+    }
+}
+
+impl<K: Eq + Hash> PartialEq for Paths<K> {
+    fn eq(&self, other: &Paths<K>) -> bool {
+        self.distances == other.distances && self.previous == other.previous
+    }
+}
+
+impl<K: Eq + Hash> Eq for Paths<K> {}
+
+impl<K: Copy + Ord + Hash> Paths<K> {
+    pub fn distance(&self, to: K) -> Option<u64> {
+        self.distances.get(&to).copied()
+    }
+
+    /// The positions from the nearest source to `to`, both included.
+    pub fn path_to(&self, to: K) -> Option<Vec<K>> {
+        self.distances.get(&to)?;
+        Some(reconstruct(&self.previous, to))
+    }
+
+    /// The reached position furthest from every source. Ties go to the
+    /// smallest position, i.e. the cell that comes first line by line.
+    pub fn farthest(&self) -> Option<(K, u64)> {
+        self.distances.iter()
+            .map(|(c, d)| (*c, *d))
+            .max_by_key(|(c, d)| (*d, Reverse(*c)))
+    }
+}
+
+fn reconstruct<K: Copy + Eq + Hash>(previous: &HashMap<K, K>, to: K) -> Vec<K> {
+    let mut path = vec![to];
+    while let Some(p) = previous.get(path.last().unwrap()) {
+        path.push(*p);
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search outwards from all of `starts` at once, so each
+/// distance is to the nearest of them. `moves(at)` lists the positions one
+/// step away from `at`; nothing more than `limit` steps away is explored.
+pub fn bfs_by<K, I, M, N>(starts: I, limit: u64, moves: M) -> Paths<K>
+    where K: Copy + Eq + Hash, I: IntoIterator<Item = K>, M: Fn(K) -> N, N: IntoIterator<Item = K>
+{
+    let mut paths = Paths::default();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if paths.distances.insert(s, 0).is_none() { queue.push_back(s) }
+    }
+    while let Some(at) = queue.pop_front() {
+        let d = paths.distances[&at];
+        if d == limit { continue }
+        for next in moves(at) {
+            if paths.distances.contains_key(&next) { continue }
+            paths.distances.insert(next, d + 1);
+            paths.previous.insert(next, at);
+            queue.push_back(next);
+        }
+    }
+    paths
+}
+
+/// Cheapest paths from the nearest of `starts`, leaving out anything that
+/// costs more than `limit`. `moves(at)` lists the positions one step away
+/// from `at` with what the step costs.
+pub fn dijkstra_by<K, I, M, N>(starts: I, limit: u64, moves: M) -> Paths<K>
+    where K: Copy + Ord + Hash, I: IntoIterator<Item = K>, M: Fn(K) -> N, N: IntoIterator<Item = (K, u64)>
+{
+    let mut paths = Paths::default();
+    let mut queue = BinaryHeap::new();
+    for s in starts {
+        paths.distances.insert(s, 0);
+        queue.push(Reverse((0, s)));
+    }
+    while let Some(Reverse((d, at))) = queue.pop() {
+        if paths.distances.get(&at).is_some_and(|best| *best < d) { continue }
+        for (next, step) in moves(at) {
+            let nd = d + step;
+            if nd <= limit && paths.distances.get(&next).is_none_or(|best| nd < *best) {
+                paths.distances.insert(next, nd);
+                paths.previous.insert(next, at);
+                queue.push(Reverse((nd, next)));
+            }
+        }
+    }
+    paths
+}
+
+/// Cheapest path from `start` to `goal` and its cost, or `None` if there is
+/// none costing at most `limit`. `moves` is as for `dijkstra_by`, and
+/// `heuristic` must never overestimate the remaining cost.
+pub fn astar_by<K, M, N, H>(start: K, goal: K, limit: u64, moves: M, heuristic: H) -> Option<(u64, Vec<K>)>
+    where K: Copy + Ord + Hash, M: Fn(K) -> N, N: IntoIterator<Item = (K, u64)>, H: Fn(K) -> u64
+{
+    let mut best: HashMap<K, u64> = HashMap::from([(start, 0)]);
+    let mut previous = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+    while let Some(Reverse((_, d, at))) = queue.pop() {
+        if at == goal { return Some((d, reconstruct(&previous, goal))) }
+        if best.get(&at).is_some_and(|b| *b < d) { continue }
+        for (next, step) in moves(at) {
+            let nd = d + step;
+            let estimate = nd.saturating_add(heuristic(next));
+            if estimate <= limit && best.get(&next).is_none_or(|b| nd < *b) {
+                best.insert(next, nd);
+                previous.insert(next, at);
+                queue.push(Reverse((estimate, nd, next)));
+            }
+        }
+    }
+    None
+}
+
+/// The searches above over a grid's orthogonal neighbours, with no limit.
+impl<T> TextGrid<T> {
+    pub fn bfs<F>(&self, start: Coord, passable: F) -> Paths
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> bool
+    {
+        self.bfs_multi([start], passable)
+    }
+
+    /// Breadth-first search outwards from all of `starts` at once, so each
+    /// distance is to the nearest of them. Starts off the grid are ignored.
+    pub fn bfs_multi<I, F>(&self, starts: I, passable: F) -> Paths
+        where I: IntoIterator<Item = Coord>, F: Fn(&GridCell<T>, &GridCell<T>) -> bool
+    {
+        let passable = &passable;
+        let starts = starts.into_iter().filter(|s| self.get_cell(*s).is_some());
+        bfs_by(starts, u64::MAX, |at| {
+            let from = self.get_cell(at).unwrap();
+            self.neighbours4(at).filter(move |to| passable(from, to)).map(|to| to.coord())
+        })
+    }
+
+    pub fn dijkstra<F>(&self, start: Coord, cost: F) -> Paths
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> Option<u64>
+    {
+        self.dijkstra_multi([start], cost)
+    }
+
+    /// Cheapest paths from the nearest of `starts`. `cost` returns `None`
+    /// for moves that are not allowed.
+    pub fn dijkstra_multi<I, F>(&self, starts: I, cost: F) -> Paths
+        where I: IntoIterator<Item = Coord>, F: Fn(&GridCell<T>, &GridCell<T>) -> Option<u64>
+    {
+        let cost = &cost;
+        let starts = starts.into_iter().filter(|s| self.get_cell(*s).is_some());
+        dijkstra_by(starts, u64::MAX, |at| {
+            let from = self.get_cell(at).unwrap();
+            self.neighbours4(at).filter_map(move |to| Some((to.coord(), cost(from, to)?)))
+        })
+    }
+
+    /// Cheapest path from `start` to `goal` and its cost, guided by
+    /// `heuristic`, which must never overestimate the remaining cost (e.g.
+    /// `Coord::manhattan` to the goal when every move costs at least 1).
+    pub fn astar<F, H>(&self, start: Coord, goal: Coord, cost: F, heuristic: H) -> Option<(u64, Vec<Coord>)>
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> Option<u64>, H: Fn(Coord) -> u64
+    {
+        self.get_cell(start)?;
+        let cost = &cost;
+        astar_by(start, goal, u64::MAX, |at| {
+            let from = self.get_cell(at).unwrap();
+            self.neighbours4(at).filter_map(move |to| Some((to.coord(), cost(from, to)?)))
+        }, heuristic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    fn grid(s: &str) -> TextGrid<u64> {
+        TextGrid::from_lines(&to_lines(s))
+    }
+
+    fn open(_: &GridCell<u64>, to: &GridCell<u64>) -> bool {
+        to.value != '#'
+    }
+
+    /// Digits cost their value to enter, `#` is a wall.
+    fn digit_cost(_: &GridCell<u64>, to: &GridCell<u64>) -> Option<u64> {
+        to.value.to_digit(10).map(u64::from)
+    }
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    #[test]
+    fn bfs_distances_and_paths() {
+        let g = grid(MAZE);
+        let paths = g.bfs(Coord::new(0, 0), open);
+        assert_eq!(paths.distance(Coord::new(4, 7)), Some(15));
+        assert_eq!(paths.distance(Coord::new(0, 3)), None);
+        let path = paths.path_to(Coord::new(4, 7)).unwrap();
+        assert_eq!((path.first(), path.last(), path.len()), (Some(&Coord::new(0, 0)), Some(&Coord::new(4, 7)), 16));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+        assert_eq!(paths.farthest(), Some((Coord::new(4, 7), 15)));
+    }
+
+    #[test]
+    fn multi_source_bfs_measures_to_the_nearest_source() {
+        let g = grid(".....\n.....");
+        let paths = g.bfs_multi([Coord::new(0, 0), Coord::new(1, 4)], open);
+        assert_eq!(paths.distance(Coord::new(0, 4)), Some(1));
+        assert_eq!(paths.distance(Coord::new(1, 2)), Some(2));
+        assert_eq!(paths.path_to(Coord::new(0, 3)), Some(vec![Coord::new(1, 4), Coord::new(0, 4), Coord::new(0, 3)]));
+        assert_eq!(g.bfs_multi([Coord::new(9, 9)], open), Paths::default());
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        let g = grid("1163751\n1381373\n2136511\n3694931\n7463417\n1319128\n1359912");
+        let paths = g.dijkstra(Coord::new(0, 0), digit_cost);
+        assert_eq!(paths.distance(Coord::new(6, 6)), Some(28));
+        let (cost, path) = g.astar(Coord::new(0, 0), Coord::new(6, 6), digit_cost, |c| c.manhattan(Coord::new(6, 6)) as u64).unwrap();
+        assert_eq!(cost, 28);
+        assert_eq!(path.iter().skip(1).map(|c| g.get_cell(*c).unwrap().value.to_digit(10).unwrap() as u64).sum::<u64>(), 28);
+    }
+
+    #[test]
+    fn astar_reports_unreachable_goals() {
+        let g = grid("1#1\n1#1");
+        assert_eq!(g.astar(Coord::new(0, 0), Coord::new(0, 2), digit_cost, |c| c.manhattan(Coord::new(0, 2)) as u64), None);
+        assert_eq!(g.astar(Coord::new(0, 0), Coord::new(0, 0), digit_cost, |_| 0), Some((0, vec![Coord::new(0, 0)])));
+    }
+
+    #[test]
+    fn searches_take_any_neighbour_function() {
+        // The number line, where each step goes one or three further.
+        let moves = |n: i64| [(n + 1, 1), (n + 3, 1)];
+        let paths = bfs_by([0], 3, |n| moves(n).map(|(m, _)| m));
+        assert_eq!((paths.distance(9), paths.distance(10)), (Some(3), None));
+        assert_eq!(paths.path_to(7), Some(vec![0, 1, 4, 7]));
+        assert_eq!(dijkstra_by([0], 3, moves).distances, paths.distances);
+        let (cost, path) = astar_by(0, 10, 4, moves, |n| (10 - n).max(0) as u64 / 3).unwrap();
+        assert_eq!((cost, path.first(), path.last(), path.len()), (4, Some(&0), Some(&10), 5));
+        assert!(path.windows(2).all(|p| [1, 3].contains(&(p[1] - p[0]))));
+        assert_eq!(astar_by(0, 10, 3, moves, |_| 0), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Areas of closed lattice paths given as grid coordinates, as
+//! produced by walking a loop on a grid. The last point connects back to the
+//! first. Points may be one tile apart or the corners of longer straight runs.
+
+use std::collections::{BTreeMap, HashSet};
+
+use super::coord::Coord;
+use super::number_theory::gcd;
+
+fn edges(path: &[Coord]) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+    let point = |p: &Coord| (p.line as i64, p.offset as i64);
+    path.iter().zip(path.iter().cycle().skip(1)).map(move |(a, b)| (point(a), point(b)))
+}
+
+/// Twice the enclosed area, by the shoelace formula. Doubling keeps the
+/// result an integer for any lattice polygon.
+pub fn double_area(path: &[Coord]) -> u64 {
+    let sum: i64 = edges(path).map(|((y1, x1), (y2, x2))| x1*y2 - x2*y1).sum();
+    sum.unsigned_abs()
+}
+
+pub fn area(path: &[Coord]) -> f64 {
+    double_area(path) as f64 / 2.0
+}
+
+/// Number of lattice points on the path itself.
+pub fn boundary_points(path: &[Coord]) -> u64 {
+    edges(path)
+        .map(|((y1, x1), (y2, x2))| gcd(y1.abs_diff(y2), x1.abs_diff(x2)))
+        .sum()
+}
+
+/// Number of lattice points strictly inside the path, i.e. grid tiles
+/// enclosed by a loop. Uses Pick's theorem, `A = I + B/2 - 1`, which only
+/// holds for paths that do not cross or retrace themselves; a path that
+/// doubles back on itself, like a flat line, encloses nothing.
+pub fn interior_points(path: &[Coord]) -> u64 {
+    if path.len() < 3 { return 0 }
+    (double_area(path) + 2).saturating_sub(boundary_points(path)) / 2
+}
+
+/// Same as `interior_points`, but by scanning each line and flipping
+/// between outside and inside at every vertical edge. `None` if any edge is
+/// diagonal.
+pub fn interior_points_scanline(path: &[Coord]) -> Option<u64> {
+    Some(enclosed_tiles(path)?.len() as u64)
+}
+
+/// The lattice points strictly inside the path, line by line. Only the upper
+/// half of each tile is considered when crossing an edge, which is what lets
+/// tiles "squeeze" between two parallel pipes without being counted. `None`
+/// if any edge is diagonal, since those cut tiles in half.
+pub fn enclosed_tiles(path: &[Coord]) -> Option<Vec<Coord>> {
+    if edges(path).any(|((y1, x1), (y2, x2))| y1 != y2 && x1 != x2) { return None }
+    if path.len() < 3 { return Some(vec![]) }
+    let mut boundary: HashSet<(i64, i64)> = HashSet::new();
+    // Line -> offsets of vertical edges spanning from the line above into it.
+    let mut crossings: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+    for ((y1, x1), (y2, x2)) in edges(path) {
+        for y in y1.min(y2)..=y1.max(y2) {
+            for x in x1.min(x2)..=x1.max(x2) {
+                boundary.insert((y, x));
+            }
+        }
+        if x1 == x2 {
+            for y in y1.min(y2)+1..=y1.max(y2) {
+                crossings.entry(y).or_default().push(x1);
+            }
+        }
+    }
+
+    let mut inside = vec![];
+    for (y, xs) in crossings.iter_mut() {
+        xs.sort();
+        for pair in xs.chunks(2) {
+            if let [from, to] = pair {
+                inside.extend((*from..*to)
+                    .filter(|x| !boundary.contains(&(*y, *x)))
+                    .map(|x| Coord::new(*y as usize, x as usize)));
+            }
+        }
+    }
+    Some(inside)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(side: usize) -> Vec<Coord> {
+        vec![Coord::new(0, 0), Coord::new(0, side), Coord::new(side, side), Coord::new(side, 0)]
+    }
+
+    fn unit_steps(corners: &[Coord]) -> Vec<Coord> {
+        let mut path = vec![];
+        for (a, b) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+            let mut at = *a;
+            while at != *b {
+                path.push(at);
+                let toward = |from: usize, to: usize| from + (from < to) as usize - (from > to) as usize;
+                at = Coord::new(toward(at.line, b.line), toward(at.offset, b.offset));
+            }
+        }
+        path
+    }
+
+    #[test]
+    fn square_by_corners_or_by_steps() {
+        for path in [square(4), unit_steps(&square(4))] {
+            assert_eq!(double_area(&path), 32);
+            assert_eq!(area(&path), 16.0);
+            assert_eq!(boundary_points(&path), 16);
+            assert_eq!(interior_points(&path), 9);
+            assert_eq!(interior_points_scanline(&path), Some(9));
+        }
+    }
+
+    #[test]
+    fn orientation_does_not_matter() {
+        let mut path = unit_steps(&[Coord::new(0, 0), Coord::new(0, 5), Coord::new(3, 5), Coord::new(3, 2), Coord::new(6, 2), Coord::new(6, 0)]);
+        let forwards = (interior_points(&path), interior_points_scanline(&path));
+        path.reverse();
+        assert_eq!(forwards, (interior_points(&path), interior_points_scanline(&path)));
+        assert_eq!(forwards, (11, Some(11)));
+    }
+
+    #[test]
+    fn touching_walls_enclose_nothing_between_them() {
+        // A U shape whose inner walls are adjacent: only the arms count.
+        let path = unit_steps(&[Coord::new(0, 0), Coord::new(0, 2), Coord::new(3, 2), Coord::new(3, 3), Coord::new(0, 3), Coord::new(0, 5), Coord::new(4, 5), Coord::new(4, 0)]);
+        assert_eq!(interior_points(&path), 6);
+        assert_eq!(enclosed_tiles(&path), Some(vec![Coord::new(1, 1), Coord::new(1, 4), Coord::new(2, 1), Coord::new(2, 4), Coord::new(3, 1), Coord::new(3, 4)]));
+    }
+
+    #[test]
+    fn degenerate_paths_enclose_nothing() {
+        assert_eq!(interior_points(&[]), 0);
+        assert_eq!(interior_points(&[Coord::new(0, 0), Coord::new(0, 1)]), 0);
+        assert_eq!(interior_points_scanline(&[Coord::new(0, 0), Coord::new(0, 1)]), Some(0));
+        let flat = [Coord::new(0, 0), Coord::new(0, 1), Coord::new(0, 2)];
+        assert_eq!((interior_points(&flat), interior_points_scanline(&flat)), (0, Some(0)));
+    }
+
+    #[test]
+    fn diagonal_edges_have_no_scanline_count() {
+        let triangle = [Coord::new(0, 0), Coord::new(0, 4), Coord::new(4, 0)];
+        assert_eq!(interior_points(&triangle), 3);
+        assert_eq!(enclosed_tiles(&triangle), None);
+        assert_eq!(interior_points_scanline(&triangle), None);
+    }
+}
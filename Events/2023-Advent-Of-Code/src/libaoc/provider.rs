@@ -0,0 +1,239 @@
+//! Where puzzle inputs come from. `Files` reads the inputs kept in `files/`,
+//! `Http` downloads them from the Advent of Code site, and `Cached` puts the
+//! two together so a download only ever happens once per day.
+
+use std::{env, fmt, fs, path::{Path, PathBuf}};
+
+use super::input::{read_day_input, resolve, LoadError};
+
+pub const YEAR: u32 = 2023;
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable holding the value of the site's `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    Load(LoadError),
+    Store { path: PathBuf, message: String },
+    NoSession { day: u32 },
+    Http { day: u32, url: String, status: Option<u16>, message: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Load(e) => write!(f, "{}", e),
+            FetchError::Store { path, message } =>
+                write!(f, "could not store input in {}: {}", path.display(), message),
+            FetchError::NoSession { day } =>
+                write!(f, "no input for day {} and {} is not set, so it cannot be downloaded", day, SESSION_VAR),
+            FetchError::Http { day, url, status: Some(status), message } =>
+                write!(f, "downloading day {} from {} failed with {}: {}", day, url, status, message),
+            FetchError::Http { day, url, status: None, message } =>
+                write!(f, "downloading day {} from {} failed: {}", day, url, message),
+        }
+    }
+}
+
+impl From<LoadError> for FetchError {
+    fn from(e: LoadError) -> FetchError {
+        FetchError::Load(e)
+    }
+}
+
+pub trait InputProvider {
+    /// The full puzzle input for `day`.
+    fn input(&self, day: u32) -> Result<String, FetchError>;
+}
+
+/// Inputs stored as `NN-input.txt` in a directory.
+#[derive(Debug, Clone)]
+pub struct Files {
+    pub dir: PathBuf
+}
+
+impl Files {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Files {
+        Files { dir: dir.as_ref().to_path_buf() }
+    }
+
+    /// The crate's own `files/` directory.
+    pub fn crate_files() -> Files {
+        Files::new(resolve("files"))
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("{:02}-input.txt", day))
+    }
+
+    pub fn has(&self, day: u32) -> bool {
+        self.path(day).is_file()
+    }
+
+    pub fn store(&self, day: u32, input: &str) -> Result<(), FetchError> {
+        let path = self.path(day);
+        let stored = fs::create_dir_all(&self.dir).and_then(|_| fs::write(&path, input));
+        stored.map_err(|e| FetchError::Store { path, message: e.to_string() })
+    }
+}
+
+impl InputProvider for Files {
+    fn input(&self, day: u32) -> Result<String, FetchError> {
+        Ok(read_day_input(day, &self.path(day).to_string_lossy())?)
+    }
+}
+
+/// Downloads inputs using a logged-in session cookie.
+#[derive(Debug, Clone)]
+pub struct Http {
+    pub base_url: String,
+    pub year: u32,
+    pub session: Option<String>
+}
+
+impl Http {
+    pub fn new(base_url: &str, session: Option<String>) -> Http {
+        Http { base_url: base_url.trim_end_matches('/').to_string(), year: YEAR, session }
+    }
+
+    /// The real site, with the session taken from `AOC_SESSION`.
+    pub fn from_env() -> Http {
+        let session = env::var(SESSION_VAR).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        Http::new(BASE_URL, session)
+    }
+
+    pub fn url(&self, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, self.year, day)
+    }
+}
+
+impl InputProvider for Http {
+    fn input(&self, day: u32) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::NoSession { day })?;
+        let url = self.url(day);
+        let failed = |status, message: String| FetchError::Http { day, url: url.clone(), status, message };
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", "advent-of-code-2023 input fetcher")
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(status, response) =>
+                    failed(Some(status), response.into_string().unwrap_or_default().trim().to_string()),
+                ureq::Error::Transport(t) => failed(None, t.to_string()),
+            })?;
+        response.into_string().map_err(|e| failed(None, e.to_string()))
+    }
+}
+
+/// Serves inputs from `files`, asking `upstream` only for days it does not
+/// have yet and keeping whatever it returns.
+pub struct Cached<P: InputProvider> {
+    pub files: Files,
+    pub upstream: P
+}
+
+impl<P: InputProvider> Cached<P> {
+    pub fn new(files: Files, upstream: P) -> Cached<P> {
+        Cached { files, upstream }
+    }
+}
+
+impl<P: InputProvider> InputProvider for Cached<P> {
+    fn input(&self, day: u32) -> Result<String, FetchError> {
+        if self.files.has(day) {
+            return self.files.input(day)
+        }
+        let input = self.upstream.input(day)?;
+        self.files.store(day, &input)?;
+        Ok(input)
+    }
+}
+
+/// The crate's `files/`, filled from the site when a day is missing.
+pub fn default_provider() -> Cached<Http> {
+    Cached::new(Files::crate_files(), Http::from_env())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A one-route HTTP server on a free local port. Records the request
+    /// line and cookie of everything it is sent.
+    fn stub_server(status: &'static str, body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(vec![]));
+        let log = seen.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = vec![];
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" { break }
+                    let line = line.trim_end().to_string();
+                    if request.is_empty() || line.to_lowercase().starts_with("cookie:") {
+                        request.push(line);
+                    }
+                }
+                log.lock().unwrap().push(request.join(" | "));
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+            }
+        });
+        (url, seen)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-2023-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn downloads_with_the_session_cookie() {
+        let (url, seen) = stub_server("200 OK", "1 2 3\n");
+        let http = Http::new(&url, Some(String::from("abc")));
+        assert_eq!(http.input(7), Ok(String::from("1 2 3\n")));
+        assert_eq!(*seen.lock().unwrap(), vec![String::from("GET /2023/day/7/input HTTP/1.1 | Cookie: session=abc")]);
+    }
+
+    #[test]
+    fn reports_http_failures_and_missing_sessions() {
+        let (url, _) = stub_server("400 Bad Request", "Please log in");
+        let err = Http::new(&url, Some(String::from("old"))).input(3).unwrap_err();
+        assert_eq!(err, FetchError::Http { day: 3, url: format!("{}/2023/day/3/input", url), status: Some(400), message: String::from("Please log in") });
+        assert_eq!(Http::new(&url, None).input(3), Err(FetchError::NoSession { day: 3 }));
+    }
+
+    #[test]
+    fn cache_only_downloads_once() {
+        let dir = temp_dir("cache");
+        let (url, seen) = stub_server("200 OK", "fresh\n");
+        let cached = Cached::new(Files::new(&dir), Http::new(&url, Some(String::from("abc"))));
+
+        assert_eq!(cached.input(1), Ok(String::from("fresh\n")));
+        assert_eq!(cached.input(1), Ok(String::from("fresh\n")));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert_eq!(fs::read_to_string(dir.join("01-input.txt")).unwrap(), "fresh\n");
+
+        // Days already on disk are never requested at all.
+        Files::new(&dir).store(2, "kept\n").unwrap();
+        assert_eq!(cached.input(2), Ok(String::from("kept\n")));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_downloads_are_not_cached() {
+        let dir = temp_dir("failed");
+        let cached = Cached::new(Files::new(&dir), Http::new("http://127.0.0.1:1", None));
+        assert_eq!(cached.input(4), Err(FetchError::NoSession { day: 4 }));
+        assert!(!Files::new(&dir).has(4));
+    }
+}
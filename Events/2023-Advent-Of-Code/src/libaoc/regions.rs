@@ -0,0 +1,178 @@
+//! Regions of a `TextGrid`: everything reachable from a cell, and the
+//! grid split into connected components.
+
+use std::{collections::{HashSet, VecDeque}, fmt::Debug, rc::Rc};
+
+use super::{Connectivity, Coord, GridCell, TextGrid};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub id: usize,
+    /// The character of the cell the component was grown from.
+    pub value: char,
+    /// In the order they were reached, starting from the top-left-most cell.
+    pub cells: Vec<Coord>,
+    members: HashSet<Coord>,
+    /// Top-left and bottom-right corners, inclusive.
+    pub bounds: (Coord, Coord),
+    /// Cell sides not shared with another cell of the component, counting
+    /// the edge of the grid. Always measured orthogonally, even for
+    /// components joined diagonally.
+    pub perimeter: usize
+}
+
+impl Component {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, at: Coord) -> bool {
+        self.members.contains(&at)
+    }
+
+    fn from_cells(id: usize, value: char, cells: Vec<Coord>) -> Component {
+        let lines = cells.iter().map(|c| c.line);
+        let offsets = cells.iter().map(|c| c.offset);
+        let bounds = (
+            Coord::new(lines.clone().min().unwrap(), offsets.clone().min().unwrap()),
+            Coord::new(lines.max().unwrap(), offsets.max().unwrap())
+        );
+        let members: HashSet<Coord> = cells.iter().copied().collect();
+        let shared: usize = cells.iter()
+            .map(|c| [Coord::new(c.line+1, c.offset), Coord::new(c.line, c.offset+1)].iter().filter(|n| members.contains(n)).count())
+            .sum();
+        let perimeter = 4 * cells.len() - 2 * shared;
+        Component { id, value, cells, members, bounds, perimeter }
+    }
+}
+
+impl<T> TextGrid<T> {
+    /// Every cell reachable from `start` by stepping between neighbours for
+    /// which `joins(from, to)` holds, `start` first. Empty if `start` is off
+    /// the grid.
+    pub fn flood_fill<F>(&self, start: Coord, connectivity: Connectivity, joins: F) -> Vec<Coord>
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> bool
+    {
+        if self.get_cell(start).is_none() { return vec![] }
+        let mut seen = HashSet::from([start]);
+        let mut order = vec![];
+        let mut queue = VecDeque::from([start]);
+        while let Some(at) = queue.pop_front() {
+            order.push(at);
+            let from = self.get_cell(at).unwrap();
+            for to in self.neighbours(at, connectivity) {
+                let next = to.coord();
+                if !seen.contains(&next) && joins(from, to) {
+                    seen.insert(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+}
+
+impl<T> TextGrid<T>
+    where T: Clone + Debug
+{
+    /// Splits every cell into components of cells joined by `joins` and
+    /// returns them indexed by id. `joins` should be symmetric, e.g. "both
+    /// cells hold the same character".
+    ///
+    /// Every entity already on the grid is removed, then each component
+    /// becomes one entity covering its cells, valued `label` of it. Entity
+    /// ids are therefore component ids, and labelling again replaces the
+    /// previous labels.
+    pub fn label_components<F, L>(&mut self, connectivity: Connectivity, joins: F, label: L) -> Vec<Component>
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> bool, L: Fn(&Component) -> T
+    {
+        let mut seen: HashSet<Coord> = HashSet::new();
+        let mut components = vec![];
+        for line in 0..self.cells.len() {
+            for offset in 0..self.cells[line].len() {
+                let at = Coord::new(line, offset);
+                if seen.contains(&at) { continue }
+                let cells = self.flood_fill(at, connectivity, &joins);
+                seen.extend(cells.iter().copied());
+                components.push(Component::from_cells(components.len(), self.cells[line][offset].value, cells));
+            }
+        }
+        self.clear_entities();
+        for component in &components {
+            self.add_entity_on(&component.cells, Rc::new(label(component)))
+                .expect("components are disjoint and on the grid");
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    fn grid(s: &str) -> TextGrid<u64> {
+        TextGrid::from_lines(&to_lines(s))
+    }
+
+    fn same<T>(a: &GridCell<T>, b: &GridCell<T>) -> bool {
+        a.value == b.value
+    }
+
+    fn id(c: &Component) -> u64 {
+        c.id as u64
+    }
+
+    #[test]
+    fn flood_fill_stays_in_its_region() {
+        let g = grid("aab\nabb\nbba");
+        assert_eq!(g.flood_fill(Coord::new(0, 0), Connectivity::Four, same), vec![Coord::new(0, 0), Coord::new(0, 1), Coord::new(1, 0)]);
+        assert_eq!(g.flood_fill(Coord::new(0, 0), Connectivity::Eight, same).len(), 3);
+        assert_eq!(g.flood_fill(Coord::new(0, 2), Connectivity::Eight, same).len(), 5);
+        assert!(g.flood_fill(Coord::new(7, 7), Connectivity::Four, same).is_empty());
+    }
+
+    #[test]
+    fn components_with_area_and_perimeter() {
+        let mut g = grid("AAAA\nBBCD\nBBCC\nEEEC");
+        let components = g.label_components(Connectivity::Four, same, id);
+        let summary = components.iter().map(|c| (c.value, c.size(), c.perimeter)).collect::<Vec<_>>();
+        assert_eq!(summary, vec![('A', 4, 10), ('B', 4, 8), ('C', 4, 10), ('D', 1, 4), ('E', 3, 8)]);
+        assert_eq!(components[2].bounds, (Coord::new(1, 2), Coord::new(3, 3)));
+        assert!(components[2].contains(Coord::new(2, 3)) && !components[2].contains(Coord::new(1, 3)));
+        assert_eq!(g.get_entity(Coord::new(3, 3)), Some(Rc::new(2)));
+        assert_eq!(g.get_entity(Coord::new(1, 3)), Some(Rc::new(3)));
+        assert_eq!(g.entities.len(), 5);
+        assert!(components.iter().all(|c| g.entity(c.id).is_some_and(|e| e.cell_count() == c.size() && c.cells.iter().all(|at| e.contains(*at)))));
+        assert_eq!(g.entities_in(g.get_cells(Coord::new(1, 2), Coord::new(2, 3))).iter().map(|e| *e.value).collect::<Vec<_>>(), vec![2, 3]);
+
+        // Labels are ordinary entities, so copies of the grid get their own.
+        let tiled = g.tile(2, 1).unwrap();
+        let (left, right) = (tiled.entity_at(Coord::new(0, 0)).unwrap(), tiled.entity_at(Coord::new(0, 4)).unwrap());
+        assert_ne!(left.id, right.id);
+        assert_eq!((*left.value, *right.value), (0, 0));
+    }
+
+    #[test]
+    fn diagonal_connectivity_merges_touching_corners() {
+        let mut g = grid("x.\n.x");
+        let four = g.label_components(Connectivity::Four, same, id);
+        assert_eq!(four.len(), 4);
+        assert_eq!(g.get_entity(Coord::new(1, 1)), Some(Rc::new(3)));
+        let eight = g.label_components(Connectivity::Eight, same, id);
+        assert_eq!(eight.len(), 2);
+        assert_eq!((eight[0].size(), eight[0].perimeter), (2, 8));
+        assert_eq!(g.get_entity(Coord::new(1, 1)), Some(Rc::new(0)));
+        assert_eq!(g.entities.len(), 2);
+        assert_eq!(g.entity_at(Coord::new(1, 1)).map(|e| e.id), Some(0));
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let mut g = grid("aaa\na\naa");
+        let components = g.label_components(Connectivity::Four, same, id);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].perimeter, 14);
+        assert_eq!(components[0].bounds, (Coord::new(0, 0), Coord::new(2, 2)));
+    }
+}
@@ -0,0 +1,133 @@
+//! Drawing a `TextGrid` back out as text, optionally translating characters
+//! (e.g. pipes to box-drawing glyphs) and picking out sets of cells.
+
+use std::collections::HashSet;
+
+use super::{Coord, TextGrid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Text only; highlights can still replace glyphs with a fill character.
+    Plain,
+    /// Highlights are coloured with ANSI escape codes.
+    Ansi
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan
+}
+
+impl Colour {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Highlight {
+    pub cells: HashSet<Coord>,
+    pub colour: Colour,
+    /// Drawn instead of the cell's own glyph, in either style.
+    pub fill: Option<char>
+}
+
+/// How to draw a grid. Cells missing from `table` are drawn as they are.
+/// When a cell is in several highlights, the first one wins.
+#[derive(Debug, Clone)]
+pub struct Render<'a> {
+    pub table: &'a [(char, char)],
+    pub highlights: Vec<Highlight>,
+    pub style: Style
+}
+
+impl<'a> Render<'a> {
+    pub fn new(table: &'a [(char, char)], style: Style) -> Render<'a> {
+        Render { table, highlights: vec![], style }
+    }
+
+    pub fn highlight<I>(mut self, cells: I, colour: Colour, fill: Option<char>) -> Render<'a>
+        where I: IntoIterator<Item = Coord>
+    {
+        self.highlights.push(Highlight { cells: cells.into_iter().collect(), colour, fill });
+        self
+    }
+
+    fn glyph(&self, c: char) -> char {
+        self.table.iter().find(|(from, _)| *from == c).map_or(c, |(_, to)| *to)
+    }
+
+    fn draw(&self, at: Coord, c: char, out: &mut String) {
+        let glyph = self.glyph(c);
+        match self.highlights.iter().find(|h| h.cells.contains(&at)) {
+            None => out.push(glyph),
+            Some(h) => {
+                let glyph = h.fill.unwrap_or(glyph);
+                match self.style {
+                    Style::Plain => out.push(glyph),
+                    Style::Ansi => out.push_str(&format!("\x1b[{}m{}\x1b[0m", h.colour.ansi_code(), glyph)),
+                }
+            }
+        }
+    }
+}
+
+impl<T> TextGrid<T> {
+    /// The grid as text, one line per row, each ending in a newline.
+    pub fn render(&self, render: &Render) -> String {
+        let mut out = String::new();
+        for row in &self.cells {
+            for cell in row {
+                render.draw(cell.coord(), cell.value, &mut out);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    const TABLE: &[(char, char)] = &[('-', '━'), ('|', '┃')];
+
+    fn grid() -> TextGrid<u64> {
+        TextGrid::from_lines(&to_lines("-.|\n.x."))
+    }
+
+    #[test]
+    fn translates_known_characters_only() {
+        assert_eq!(grid().render(&Render::new(TABLE, Style::Plain)), "━.┃\n.x.\n");
+        assert_eq!(grid().render(&Render::new(&[], Style::Ansi)), "-.|\n.x.\n");
+    }
+
+    #[test]
+    fn plain_highlights_only_show_their_fill() {
+        let render = Render::new(TABLE, Style::Plain)
+            .highlight([Coord::new(0, 0)], Colour::Red, None)
+            .highlight([Coord::new(1, 0), Coord::new(1, 2)], Colour::Green, Some('I'));
+        assert_eq!(grid().render(&render), "━.┃\nIxI\n");
+    }
+
+    #[test]
+    fn ansi_highlights_are_coloured_and_first_one_wins() {
+        let render = Render::new(TABLE, Style::Ansi)
+            .highlight([Coord::new(0, 2)], Colour::Red, None)
+            .highlight([Coord::new(0, 2), Coord::new(1, 1)], Colour::Blue, Some('#'));
+        assert_eq!(grid().render(&render), "━.\x1b[31m┃\x1b[0m\n.\x1b[34m#\x1b[0m.\n");
+    }
+}
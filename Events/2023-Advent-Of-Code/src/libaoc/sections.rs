@@ -0,0 +1,169 @@
+//! Inputs made of blocks separated by blank lines. Line endings are
+//! normalised and trailing whitespace dropped first, so a file saved with
+//! `\r\n` or with stray spaces on its "blank" lines splits the same way.
+//!
+//! Sections are taken in order and given names, which are used in errors:
+//!
+//! ```text
+//! let mut sections = Sections::read(input);
+//! let seeds = sections.next("seeds")?;
+//! let maps = sections.rest("map", 1)?;
+//! ```
+
+use std::{collections::VecDeque, fmt};
+
+use super::parse::{parse_all, Cursor, ParseError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    /// Line number of the first line in the whole input, from 1.
+    pub first_line: usize,
+    pub lines: Vec<String>
+}
+
+impl Section {
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Runs `parser` over the whole section. Errors are reported against
+    /// the line numbers of the full input.
+    pub fn parse<T, F>(&self, parser: F) -> Result<T, SectionError>
+        where F: for<'a> FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>
+    {
+        parse_all(&self.text(), parser).map_err(|mut error| {
+            error.line += self.first_line - 1;
+            SectionError::Parse { section: self.name.clone(), error }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionError {
+    /// Sections ran out, or some were left over.
+    Count { expected: usize, at_least: bool, found: usize },
+    Parse { section: String, error: ParseError },
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionError::Count { expected, at_least, found } => {
+                let bound = if *at_least { "at least " } else { "" };
+                let noun = if *expected == 1 { "section" } else { "sections" };
+                write!(f, "expected {}{} {} separated by blank lines, found {}", bound, expected, noun, found)
+            }
+            SectionError::Parse { section, error } => write!(f, "in {}: {}", section, error),
+        }
+    }
+}
+
+/// The sections of an input not taken yet.
+#[derive(Debug, Clone)]
+pub struct Sections {
+    remaining: VecDeque<(usize, Vec<String>)>,
+    taken: usize,
+    total: usize
+}
+
+impl Sections {
+    pub fn read(input: &str) -> Sections {
+        let mut remaining = VecDeque::new();
+        let mut current: Option<(usize, Vec<String>)> = None;
+        // `lines` already strips a trailing `\r`; trimming catches the rest.
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim_end();
+            match (&mut current, line.is_empty()) {
+                (None, true) => {}
+                (None, false) => current = Some((i + 1, vec![String::from(line)])),
+                (Some((_, lines)), false) => lines.push(String::from(line)),
+                (Some(_), true) => remaining.extend(current.take()),
+            }
+        }
+        remaining.extend(current);
+        let total = remaining.len();
+        Sections { remaining, taken: 0, total }
+    }
+
+    /// How many sections are left.
+    pub fn len(&self) -> usize {
+        self.remaining.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_empty()
+    }
+
+    /// The next section, called `name`.
+    pub fn next(&mut self, name: &str) -> Result<Section, SectionError> {
+        let (first_line, lines) = self.remaining.pop_front()
+            .ok_or(SectionError::Count { expected: self.taken + 1, at_least: true, found: self.total })?;
+        self.taken += 1;
+        Ok(Section { name: String::from(name), first_line, lines })
+    }
+
+    /// Every remaining section, numbered `name 1`, `name 2`, ..., of which
+    /// there must be at least `min`.
+    pub fn rest(&mut self, name: &str, min: usize) -> Result<Vec<Section>, SectionError> {
+        if self.remaining.len() < min {
+            return Err(SectionError::Count { expected: self.taken + min, at_least: true, found: self.total });
+        }
+        let mut sections = vec![];
+        for n in 1..=self.remaining.len() {
+            sections.push(self.next(&format!("{} {}", name, n))?);
+        }
+        Ok(sections)
+    }
+
+    /// Fails if any sections were not taken.
+    pub fn end(&self) -> Result<(), SectionError> {
+        if !self.remaining.is_empty() {
+            return Err(SectionError::Count { expected: self.taken, at_least: false, found: self.total });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::parse::{lines, spaced};
+
+    #[test]
+    fn line_endings_and_blank_lines_do_not_matter() {
+        let unix = Sections::read("a\nb\n\nc\n");
+        let windows = Sections::read("\r\n  \r\na  \r\nb\r\n \t\r\n\r\n\r\nc");
+        for mut s in [unix, windows] {
+            assert_eq!(s.len(), 2);
+            assert_eq!(s.next("first").unwrap().lines, vec!["a", "b"]);
+            assert_eq!(s.next("second").unwrap().text(), "c");
+            assert!(s.end().is_ok());
+        }
+    }
+
+    #[test]
+    fn counts_are_checked() {
+        let mut s = Sections::read("a\n\nb");
+        assert_eq!(s.rest("block", 3).unwrap_err(), SectionError::Count { expected: 3, at_least: true, found: 2 });
+        s.next("a").unwrap();
+        s.next("b").unwrap();
+        assert_eq!(s.next("c").unwrap_err().to_string(), "expected at least 3 sections separated by blank lines, found 2");
+
+        let mut s = Sections::read("a\n\nb\n\nc");
+        s.next("a").unwrap();
+        assert_eq!(s.end().unwrap_err().to_string(), "expected 1 section separated by blank lines, found 3");
+        let rest = s.rest("block", 1).unwrap();
+        assert_eq!(rest.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["block 1", "block 2"]);
+    }
+
+    #[test]
+    fn parse_errors_point_into_the_whole_input() {
+        let mut s = Sections::read("x: 1\r\n\r\n1 2\r\n3 y\r\n");
+        s.next("header").unwrap();
+        let body = s.next("body").unwrap();
+        assert_eq!(body.first_line, 3);
+        let e = body.parse(|c| lines(c, |c| spaced(c, |c| c.number::<u32>()))).unwrap_err();
+        assert_eq!(e.to_string(), "in body: line 4, column 3: expected end of input, found 'y'");
+    }
+}
@@ -0,0 +1,232 @@
+//! A grid that only stores the cells that hold something, addressed by
+//! signed coordinates. For spaces that are huge and mostly empty, or that
+//! grow in every direction, where a `TextGrid` would be wasteful or could
+//! not go negative.
+
+use std::{collections::{hash_map, HashMap}, fmt::Debug};
+
+use super::{Connectivity, Coord, GridCell, TextGrid, NEIGHBOURS4, NEIGHBOURS8};
+use super::coord::shift;
+use super::render::Render;
+
+pub use super::coord::Point;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<V> {
+    cells: HashMap<Point, V>,
+    /// Top-left and bottom-right corners, inclusive. `None` when empty.
+    bounds: Option<(Point, Point)>
+}
+
+impl<V> Default for SparseGrid<V> {
+    fn default() -> SparseGrid<V> {
+        SparseGrid { cells: HashMap::new(), bounds: None }
+    }
+}
+
+impl<V> FromIterator<(Point, V)> for SparseGrid<V> {
+    fn from_iter<I: IntoIterator<Item = (Point, V)>>(iter: I) -> SparseGrid<V> {
+        let mut grid = SparseGrid::new();
+        for (p, v) in iter {
+            grid.insert(p, v);
+        }
+        grid
+    }
+}
+
+impl<V> SparseGrid<V> {
+    pub fn new() -> SparseGrid<V> {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Top-left and bottom-right corners of the occupied cells, inclusive.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Lines and columns spanned by `bounds`, empty cells included.
+    pub fn size(&self) -> (usize, usize) {
+        match self.bounds {
+            None => (0, 0),
+            Some((min, max)) => ((max.0 - min.0 + 1) as usize, (max.1 - min.1 + 1) as usize),
+        }
+    }
+
+    /// Stores `value` at `at`, returning what was there before.
+    pub fn insert(&mut self, at: Point, value: V) -> Option<V> {
+        self.bounds = Some(match self.bounds {
+            None => (at, at),
+            Some((min, max)) => ((min.0.min(at.0), min.1.min(at.1)), (max.0.max(at.0), max.1.max(at.1))),
+        });
+        self.cells.insert(at, value)
+    }
+
+    /// Empties the cell at `at`. The bounds shrink if it was on their edge.
+    pub fn remove(&mut self, at: Point) -> Option<V> {
+        let value = self.cells.remove(&at)?;
+        if let Some((min, max)) = self.bounds {
+            if at.0 == min.0 || at.0 == max.0 || at.1 == min.1 || at.1 == max.1 {
+                self.bounds = self.cells.keys().fold(None, |b, p| Some(match b {
+                    None => (*p, *p),
+                    Some((min, max)) => ((min.0.min(p.0), min.1.min(p.1)), (max.0.max(p.0), max.1.max(p.1))),
+                }));
+            }
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, at: Point) -> Option<&V> {
+        self.cells.get(&at)
+    }
+
+    pub fn get_mut(&mut self, at: Point) -> Option<&mut V> {
+        self.cells.get_mut(&at)
+    }
+
+    pub fn contains(&self, at: Point) -> bool {
+        self.cells.contains_key(&at)
+    }
+
+    /// Occupied cells in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, V> {
+        self.cells.iter()
+    }
+
+    /// Occupied cells north, east, south and west of `center`, in that
+    /// order.
+    pub fn neighbours4(&self, center: Point) -> impl Iterator<Item = (Point, &V)> + '_ {
+        NEIGHBOURS4.into_iter().filter_map(move |d| self.occupied(shift(center, d)))
+    }
+
+    /// Occupied cells of the eight around `center`, line by line.
+    pub fn neighbours8(&self, center: Point) -> impl Iterator<Item = (Point, &V)> + '_ {
+        NEIGHBOURS8.into_iter().filter_map(move |d| self.occupied(shift(center, d)))
+    }
+
+    pub fn neighbours(&self, center: Point, connectivity: Connectivity) -> Vec<(Point, &V)> {
+        match connectivity {
+            Connectivity::Four => self.neighbours4(center).collect(),
+            Connectivity::Eight => self.neighbours8(center).collect(),
+        }
+    }
+
+    /// Occupied cells within `radius` of `center` (including it), line by
+    /// line.
+    pub fn window(&self, center: Point, radius: usize) -> impl Iterator<Item = (Point, &V)> + '_ {
+        let r = radius as i64;
+        (center.0 - r..=center.0 + r)
+            .flat_map(move |l| (center.1 - r..=center.1 + r).map(move |o| (l, o)))
+            .filter_map(move |p| self.occupied(p))
+    }
+
+    fn occupied(&self, at: Point) -> Option<(Point, &V)> {
+        self.cells.get(&at).map(|v| (at, v))
+    }
+
+    /// Where `at` lands in the grid built by `to_text_grid`, if it is
+    /// within the bounds.
+    pub fn local(&self, at: Point) -> Option<Coord> {
+        let (min, max) = self.bounds?;
+        if at.0 < min.0 || at.0 > max.0 || at.1 < min.1 || at.1 > max.1 { return None }
+        Some(Coord::new((at.0 - min.0) as usize, (at.1 - min.1) as usize))
+    }
+
+    /// Copies the cells of `grid` that `pick` keeps, at the same
+    /// coordinates.
+    pub fn from_text_grid<T, F>(grid: &TextGrid<T>, pick: F) -> SparseGrid<V>
+        where F: Fn(&GridCell<T>) -> Option<V>
+    {
+        grid.cells.iter().flatten()
+            .filter_map(|gc| pick(gc).map(|v| ((gc.line as i64, gc.offset as i64), v)))
+            .collect()
+    }
+
+    /// The bounding box drawn as a rectangular `TextGrid`, with its
+    /// top-left corner at `(0, 0)` (see `local`). Occupied cells are drawn
+    /// with `glyph`, the rest with `background`.
+    pub fn to_text_grid<T, F>(&self, glyph: F, background: char) -> TextGrid<T>
+        where T: Clone + Debug, F: Fn(&V) -> char
+    {
+        let (height, width) = self.size();
+        let origin = self.bounds.map_or((0, 0), |(min, _)| min);
+        let lines: Vec<String> = (0..height as i64)
+            .map(|l| (0..width as i64)
+                .map(|o| self.get((origin.0 + l, origin.1 + o)).map_or(background, &glyph))
+                .collect())
+            .collect();
+        TextGrid::from_lines(&lines)
+    }
+
+    /// Draws the grid as `to_text_grid` lays it out, so highlights are in
+    /// `local` coordinates.
+    pub fn render<F>(&self, render: &Render, glyph: F, background: char) -> String
+        where F: Fn(&V) -> char
+    {
+        self.to_text_grid::<(), F>(glyph, background).render(render)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::render::{Colour, Style};
+    use crate::libaoc::to_lines;
+
+    fn galaxies() -> SparseGrid<char> {
+        [((-2, 3), '#'), ((0, 0), '#'), ((1, -1), 'x')].into_iter().collect()
+    }
+
+    #[test]
+    fn bounds_follow_inserts_and_removals() {
+        let mut g = galaxies();
+        assert_eq!(g.bounds(), Some(((-2, -1), (1, 3))));
+        assert_eq!(g.size(), (4, 5));
+        assert_eq!(g.insert((0, 0), '.'), Some('#'));
+        g.insert((1_000_000, 0), '#');
+        assert_eq!(g.bounds(), Some(((-2, -1), (1_000_000, 3))));
+        assert_eq!(g.remove((1_000_000, 0)), Some('#'));
+        assert_eq!(g.remove((1, -1)), Some('x'));
+        assert_eq!(g.bounds(), Some(((-2, 0), (0, 3))));
+        assert_eq!(g.remove((7, 7)), None);
+        assert_eq!(SparseGrid::<char>::new().size(), (0, 0));
+    }
+
+    #[test]
+    fn neighbours_skip_empty_cells() {
+        let g = galaxies();
+        assert_eq!(g.neighbours4((0, -1)).collect::<Vec<_>>(), vec![((0, 0), &'#'), ((1, -1), &'x')]);
+        assert_eq!(g.neighbours((-1, 2), Connectivity::Eight), vec![((-2, 3), &'#')]);
+        assert!(g.neighbours((-1, 2), Connectivity::Four).is_empty());
+        assert_eq!(g.neighbours8((0, 1)).count(), 1);
+        assert_eq!(g.window((-1, 1), 2).map(|(p, _)| p).collect::<Vec<_>>(), vec![(-2, 3), (0, 0), (1, -1)]);
+    }
+
+    #[test]
+    fn round_trips_through_text_grid() {
+        let g = galaxies();
+        let text: TextGrid<u64> = g.to_text_grid(|c| *c, '.');
+        assert_eq!(text.basis, vec!["....#", ".....", ".#...", "x...."]);
+        assert_eq!(g.local((0, 0)), Some(Coord::new(2, 1)));
+        assert_eq!(g.local((2, 0)), None);
+
+        let back = SparseGrid::from_text_grid(&TextGrid::<u64>::from_lines(&to_lines("#..\n..#")), |gc| (gc.value == '#').then_some(gc.value));
+        assert_eq!(back.iter().count(), 2);
+        assert!(back.contains((1, 2)));
+    }
+
+    #[test]
+    fn renders_in_local_coordinates() {
+        let g = galaxies();
+        let render = Render::new(&[('#', '*')], Style::Plain)
+            .highlight(g.local((1, -1)), Colour::Red, Some('O'));
+        assert_eq!(g.render(&render, |c| *c, ' '), "    *\n     \n *   \nO    \n");
+    }
+}
@@ -0,0 +1,264 @@
+//! Rotating, reflecting and tiling a `TextGrid`. Every transform returns a
+//! new grid whose `basis`, `cells` and entities all agree with each other.
+//! Transforms need every line to be the same length and return `None`
+//! otherwise; `padded` evens out a ragged grid first.
+
+use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+
+use super::{Coord, Entity, GridCell, TextGrid};
+
+impl<T> TextGrid<T> {
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Length of the longest line.
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn is_rectangular(&self) -> bool {
+        self.cells.iter().all(|row| row.len() == self.width())
+    }
+
+    /// A copy with every line filled out to `width()` with `fill`. The new
+    /// cells have no entity.
+    pub fn padded(&self, fill: char) -> TextGrid<T> {
+        let width = self.width();
+        let cells: Vec<Vec<GridCell<T>>> = self.cells.iter().enumerate().map(|(line, row)| {
+            (0..width).map(|offset| match row.get(offset) {
+                Some(gc) => copy_cell(gc, Coord::new(line, offset)),
+                None => GridCell { line, offset, value: fill, entity: None, entity_id: None }
+            }).collect()
+        }).collect();
+        TextGrid {
+            basis: cells.iter().map(|row| row.iter().map(|gc| gc.value).collect()).collect(),
+            cells,
+            entities: self.entities.iter().map(|e| copy_entity(e, e.id, |c| c)).collect()
+        }
+    }
+
+    /// Builds a `height` by `width` grid where the cell at `to(line, offset)`
+    /// is a copy of the cell at `(line, offset)` in this one, or `None` if
+    /// this grid is ragged.
+    fn remap<F>(&self, height: usize, width: usize, to: F) -> Option<TextGrid<T>>
+        where F: Fn(Coord) -> Coord
+    {
+        if !self.is_rectangular() { return None }
+        let mut slots: Vec<Vec<Option<GridCell<T>>>> = (0..height).map(|_| (0..width).map(|_| None).collect()).collect();
+        for gc in self.cells.iter().flatten() {
+            let at = to(gc.coord());
+            slots[at.line][at.offset] = Some(copy_cell(gc, at));
+        }
+        let cells: Vec<Vec<GridCell<T>>> = slots.into_iter()
+            .map(|row| row.into_iter().map(|gc| gc.unwrap()).collect())
+            .collect();
+        Some(TextGrid {
+            basis: cells.iter().map(|row| row.iter().map(|gc| gc.value).collect()).collect(),
+            cells,
+            entities: self.entities.iter().map(|e| copy_entity(e, e.id, &to)).collect()
+        })
+    }
+
+    /// Lines become columns: the cell at `(line, offset)` moves to
+    /// `(offset, line)`.
+    pub fn transpose(&self) -> Option<TextGrid<T>> {
+        self.remap(self.width(), self.height(), |c| Coord::new(c.offset, c.line))
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Option<TextGrid<T>> {
+        let h = self.height();
+        self.remap(self.width(), h, |c| Coord::new(c.offset, h - 1 - c.line))
+    }
+
+    pub fn rotate_180(&self) -> Option<TextGrid<T>> {
+        let (h, w) = (self.height(), self.width());
+        self.remap(h, w, |c| Coord::new(h - 1 - c.line, w - 1 - c.offset))
+    }
+
+    /// A quarter turn anticlockwise, i.e. three clockwise.
+    pub fn rotate_ccw(&self) -> Option<TextGrid<T>> {
+        let w = self.width();
+        self.remap(w, self.height(), |c| Coord::new(w - 1 - c.offset, c.line))
+    }
+
+    /// Mirrors each line, swapping left and right.
+    pub fn flip_horizontal(&self) -> Option<TextGrid<T>> {
+        let (h, w) = (self.height(), self.width());
+        self.remap(h, w, |c| Coord::new(c.line, w - 1 - c.offset))
+    }
+
+    /// Reverses the order of the lines, swapping top and bottom.
+    pub fn flip_vertical(&self) -> Option<TextGrid<T>> {
+        let (h, w) = (self.height(), self.width());
+        self.remap(h, w, |c| Coord::new(h - 1 - c.line, c.offset))
+    }
+
+    /// `across` copies side by side, repeated `down` times, or `None` if
+    /// this grid is ragged. Each copy gets its own entities, numbered tile
+    /// by tile along each line of tiles.
+    pub fn tile(&self, across: usize, down: usize) -> Option<TextGrid<T>> {
+        if !self.is_rectangular() { return None }
+        let (h, w, n) = (self.height(), self.width(), self.entities.len());
+        let mut cells = vec![];
+        for line in 0..h * down {
+            let mut row = vec![];
+            for offset in 0..w * across {
+                let gc = &self.cells[line % h][offset % w];
+                let tile = (line / h) * across + offset / w;
+                let mut copy = copy_cell(gc, Coord::new(line, offset));
+                copy.entity_id = gc.entity_id.map(|id| tile * n + id);
+                row.push(copy);
+            }
+            cells.push(row);
+        }
+        let mut entities = vec![];
+        for tile in 0..across * down {
+            let shift = ((tile / across) * h, (tile % across) * w);
+            let moved = |c: Coord| Coord::new(c.line + shift.0, c.offset + shift.1);
+            entities.extend(self.entities.iter().map(|e| copy_entity(e, tile * n + e.id, moved)));
+        }
+        Some(TextGrid {
+            basis: cells.iter().map(|row| row.iter().map(|gc| gc.value).collect()).collect(),
+            cells,
+            entities
+        })
+    }
+
+    /// A hash of the characters only, for spotting a grid returning to an
+    /// earlier state. Entities are ignored.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.basis.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+fn copy_cell<T>(gc: &GridCell<T>, at: Coord) -> GridCell<T> {
+    GridCell {
+        line: at.line,
+        offset: at.offset,
+        value: gc.value,
+        entity: gc.entity.clone(),
+        entity_id: gc.entity_id
+    }
+}
+
+/// `e` renumbered to `id`, with every cell it covers moved by `to`.
+fn copy_entity<T, F: Fn(Coord) -> Coord>(e: &Entity<T>, id: usize, to: F) -> Entity<T> {
+    let (a, b) = (to(e.start), to(e.end));
+    Entity {
+        id,
+        start: Coord::new(a.line.min(b.line), a.offset.min(b.offset)),
+        end: Coord::new(a.line.max(b.line), a.offset.max(b.offset)),
+        value: e.value.clone(),
+        shape: e.shape.as_ref().map(|cells| cells.iter().map(|c| to(*c)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    fn grid(s: &str) -> TextGrid<u64> {
+        TextGrid::from_lines(&to_lines(s))
+    }
+
+    /// Checks that `basis`, `cells` and entities describe the same grid.
+    fn assert_consistent(g: &TextGrid<u64>) {
+        for (line, row) in g.cells.iter().enumerate() {
+            assert_eq!(row.iter().map(|gc| gc.value).collect::<String>(), g.basis[line]);
+            for (offset, gc) in row.iter().enumerate() {
+                assert_eq!((gc.line, gc.offset), (line, offset));
+                if let Some(id) = gc.entity_id {
+                    assert!(g.entity(id).unwrap().contains(Coord::new(line, offset)));
+                }
+            }
+        }
+        for e in &g.entities {
+            let covered = g.cells.iter().flatten().filter(|gc| gc.entity_id == Some(e.id)).count();
+            assert_eq!(covered, e.cell_count());
+            for line in e.start.line..=e.end.line {
+                for offset in e.start.offset..=e.end.offset {
+                    let at = Coord::new(line, offset);
+                    assert_eq!(g.entity_at(at).map(|x| x.id) == Some(e.id), e.contains(at));
+                }
+            }
+        }
+    }
+
+    fn with_entity() -> TextGrid<u64> {
+        let mut g = grid("abc\ndef");
+        g.add_entity(Coord::new(0, 1), Coord::new(0, 2), Rc::new(7)).unwrap();
+        // An L whose corners surround the entity above.
+        g.add_entity_on(&[Coord::new(0, 0), Coord::new(1, 0), Coord::new(1, 1)], Rc::new(8)).unwrap();
+        g
+    }
+
+    #[test]
+    fn rotations_and_flips() {
+        let g = with_entity();
+        let cases = [
+            (g.transpose(), vec!["ad", "be", "cf"], (Coord::new(1, 0), Coord::new(2, 0))),
+            (g.rotate_cw(), vec!["da", "eb", "fc"], (Coord::new(1, 1), Coord::new(2, 1))),
+            (g.rotate_180(), vec!["fed", "cba"], (Coord::new(1, 0), Coord::new(1, 1))),
+            (g.rotate_ccw(), vec!["cf", "be", "ad"], (Coord::new(0, 0), Coord::new(1, 0))),
+            (g.flip_horizontal(), vec!["cba", "fed"], (Coord::new(0, 0), Coord::new(0, 1))),
+            (g.flip_vertical(), vec!["def", "abc"], (Coord::new(1, 1), Coord::new(1, 2))),
+        ];
+        for (t, basis, span) in cases {
+            let t = t.unwrap();
+            assert_eq!(t.basis, basis);
+            assert_eq!((t.entities[0].start, t.entities[0].end), span);
+            assert_consistent(&t);
+        }
+    }
+
+    #[test]
+    fn four_turns_or_two_flips_change_nothing() {
+        let g = with_entity();
+        let back = g.rotate_cw().and_then(|t| t.rotate_cw()?.rotate_ccw()?.rotate_cw()?.rotate_180()).unwrap();
+        assert_eq!(back.basis, g.basis);
+        assert_eq!(g.flip_horizontal().and_then(|t| t.flip_horizontal()).unwrap().basis, g.basis);
+        assert_eq!(g.transpose().and_then(|t| t.transpose()).unwrap().entities[0].start, Coord::new(0, 1));
+        assert_eq!(g.rotate_cw().and_then(|t| t.flip_horizontal()).unwrap().basis, g.transpose().unwrap().basis);
+    }
+
+    #[test]
+    fn tiling_copies_entities() {
+        let t = with_entity().tile(2, 3).unwrap();
+        assert_eq!(t.basis, vec!["abcabc", "defdef", "abcabc", "defdef", "abcabc", "defdef"]);
+        assert_eq!(t.entities.len(), 12);
+        assert_eq!(t.entity_at(Coord::new(2, 4)).map(|e| (e.id, e.start, e.end)), Some((6, Coord::new(2, 4), Coord::new(2, 5))));
+        assert_eq!(t.entity_at(Coord::new(3, 1)).map(|e| (e.id, e.cell_count())), Some((5, 3)));
+        assert_consistent(&t);
+        assert_eq!(with_entity().tile(1, 1).unwrap().basis, with_entity().basis);
+    }
+
+    #[test]
+    fn content_hash_follows_the_characters() {
+        let g = grid("#.\n.#");
+        assert_eq!(g.content_hash(), g.rotate_180().unwrap().content_hash());
+        assert_ne!(g.content_hash(), g.flip_horizontal().unwrap().content_hash());
+        assert_eq!(with_entity().content_hash(), grid("abc\ndef").content_hash());
+    }
+
+    #[test]
+    fn ragged_grids_are_padded_before_transforming() {
+        let mut g = grid("ab\nc");
+        g.set_entity(Coord::new(1, 0), Rc::new(3)).unwrap();
+        assert!(g.transpose().is_none() && g.rotate_cw().is_none() && g.tile(2, 2).is_none());
+
+        let padded = g.padded('.');
+        assert_eq!(padded.basis, vec!["ab", "c."]);
+        assert_consistent(&padded);
+        let t = padded.transpose().unwrap();
+        assert_eq!(t.basis, vec!["ac", "b."]);
+        assert_eq!(t.entity_at(Coord::new(0, 1)).map(|e| *e.value), Some(3));
+        assert_consistent(&padded.tile(2, 1).unwrap());
+    }
+}
@@ -0,0 +1,135 @@
+//! Grids whose characters decode into a domain type. The decoded values
+//! are kept beside the `TextGrid`, which keeps its original characters for
+//! rendering and its entities for other uses.
+
+use std::{any::type_name, fmt::{self, Debug}, ops::Deref};
+
+use super::{Coord, TextGrid};
+
+pub trait FromChar: Sized {
+    /// The value `c` stands for, or `None` if it is not valid in this grid.
+    fn from_char(c: char) -> Option<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellParseError {
+    pub line: usize,
+    pub offset: usize,
+    pub value: char,
+    /// Name of the type the cell should have decoded into.
+    pub expected: &'static str
+}
+
+impl fmt::Display for CellParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Lines and columns are counted from 1, as in an editor.
+        write!(f, "unexpected {:?} at line {}, column {} (not a {})", self.value, self.line+1, self.offset+1, self.expected)
+    }
+}
+
+/// A `TextGrid` with every cell decoded into a `V`. It derefs to the grid,
+/// whose entities are of type `T`.
+pub struct TypedGrid<V, T = ()> {
+    grid: TextGrid<T>,
+    values: Vec<Vec<V>>
+}
+
+impl<V, T> TypedGrid<V, T>
+    where V: FromChar, T: Clone + Debug
+{
+    pub fn parse(lines: &[String]) -> Result<TypedGrid<V, T>, CellParseError> {
+        TypedGrid::decode(TextGrid::from_lines(lines))
+    }
+
+    /// Decodes every cell of `grid`, failing on the first one, line by
+    /// line, that `V` does not accept.
+    pub fn decode(grid: TextGrid<T>) -> Result<TypedGrid<V, T>, CellParseError> {
+        let values = grid.cells.iter()
+            .map(|row| row.iter().map(|gc| V::from_char(gc.value).ok_or(CellParseError {
+                line: gc.line,
+                offset: gc.offset,
+                value: gc.value,
+                expected: type_name::<V>().rsplit("::").next().unwrap()
+            })).collect::<Result<Vec<V>, _>>())
+            .collect::<Result<Vec<Vec<V>>, _>>()?;
+        Ok(TypedGrid { grid, values })
+    }
+}
+
+impl<V, T> TypedGrid<V, T> {
+    /// The decoded value of the cell at `at`.
+    pub fn typed(&self, at: Coord) -> Option<&V> {
+        self.values.get(at.line)?.get(at.offset)
+    }
+
+    pub fn into_grid(self) -> TextGrid<T> {
+        self.grid
+    }
+}
+
+impl<V, T> Debug for TypedGrid<V, T> where V: Debug, T: Clone + Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedGrid").field("grid", &self.grid).field("values", &self.values).finish()
+    }
+}
+
+impl<V, T> Deref for TypedGrid<V, T> {
+    type Target = TextGrid<T>;
+
+    fn deref(&self) -> &TextGrid<T> {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall
+    }
+
+    impl FromChar for Tile {
+        fn from_char(c: char) -> Option<Tile> {
+            match c {
+                '.' => Some(Tile::Open),
+                '#' => Some(Tile::Wall),
+                _ => None
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_every_cell_and_keeps_the_characters() {
+        let g: TypedGrid<Tile> = TypedGrid::parse(&to_lines(".#\n#.")).unwrap();
+        assert_eq!(g.typed(Coord::new(0, 1)), Some(&Tile::Wall));
+        assert_eq!(g.typed(Coord::new(1, 1)), Some(&Tile::Open));
+        assert_eq!(g.typed(Coord::new(2, 0)), None);
+        assert_eq!(g.get_cell(Coord::new(0, 1)).unwrap().value, '#');
+        assert_eq!(g.basis, vec![".#", "#."]);
+    }
+
+    #[test]
+    fn entities_are_left_alone() {
+        let mut text: TextGrid<u64> = TextGrid::from_lines(&to_lines("..\n.#"));
+        let id = text.set_entity(Coord::new(1, 0), Rc::new(5)).unwrap();
+        let g: TypedGrid<Tile, u64> = TypedGrid::decode(text).unwrap();
+        assert!(g.get_entity(Coord::new(0, 0)).is_none());
+        assert!(g.entity_at(Coord::new(0, 0)).is_none());
+        assert_eq!(g.entity_at(Coord::new(1, 0)).map(|e| (e.id, *e.value)), Some((id, 5)));
+        assert_eq!(g.entities.len(), 1);
+        assert_eq!(g.typed(Coord::new(1, 0)), Some(&Tile::Open));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_cell() {
+        let e = TypedGrid::<Tile>::parse(&to_lines("..\n.#\n#x#")).unwrap_err();
+        assert_eq!(e, CellParseError { line: 2, offset: 1, value: 'x', expected: "Tile" });
+        assert_eq!(e.to_string(), "unexpected 'x' at line 3, column 2 (not a Tile)");
+    }
+}
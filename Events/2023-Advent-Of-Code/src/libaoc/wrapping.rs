@@ -0,0 +1,213 @@
+//! A `TextGrid` seen as tiling the plane forever in every direction.
+//! Coordinates are signed and any of them is valid: `(-1, 0)` is the last
+//! line of the grid, `(0, width)` the start of the first one again. Results
+//! keep the unwrapped coordinates, so callers can still tell copies apart.
+//!
+//! The searches are those of `path`, run over unwrapped points. The plane
+//! never runs out, so each is bounded by a `limit` on distance or cost.
+
+use std::rc::Rc;
+
+use super::{Connectivity, Coord, GridCell, TextGrid, NEIGHBOURS4, NEIGHBOURS8};
+use super::coord::{shift, Point};
+use super::path::{astar_by, bfs_by, dijkstra_by, Paths};
+
+pub struct Wrapping<'a, T> {
+    grid: &'a TextGrid<T>,
+    height: usize,
+    width: usize
+}
+
+impl<T> TextGrid<T> {
+    /// Panics if the grid is empty or its lines have different lengths.
+    pub fn wrapping(&self) -> Wrapping<'_, T> {
+        if !self.is_rectangular() { panic!("Cannot wrap a TextGrid with lines of different lengths.") }
+        if self.height() == 0 || self.width() == 0 { panic!("Cannot wrap an empty TextGrid.") }
+        Wrapping { grid: self, height: self.height(), width: self.width() }
+    }
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    /// The cell of the underlying grid that `at` lands on.
+    pub fn wrap(&self, at: Point) -> Coord {
+        Coord::new(at.0.rem_euclid(self.height as i64) as usize, at.1.rem_euclid(self.width as i64) as usize)
+    }
+
+    /// Which copy of the grid `at` is in, `(0, 0)` being the original.
+    pub fn tile_of(&self, at: Point) -> (i64, i64) {
+        (at.0.div_euclid(self.height as i64), at.1.div_euclid(self.width as i64))
+    }
+
+    pub fn get_cell(&self, at: Point) -> &'a GridCell<T> {
+        let c = self.wrap(at);
+        &self.grid.cells[c.line][c.offset]
+    }
+
+    pub fn get_entity(&self, at: Point) -> Option<Rc<T>> {
+        self.get_cell(at).entity.clone()
+    }
+
+    /// The cells north, east, south and west of `center`, in that order.
+    pub fn neighbours4(&self, center: Point) -> impl Iterator<Item = (Point, &'a GridCell<T>)> + '_ {
+        NEIGHBOURS4.into_iter().map(move |d| {
+            let p = shift(center, d);
+            (p, self.get_cell(p))
+        })
+    }
+
+    /// The eight cells around `center`, line by line.
+    pub fn neighbours8(&self, center: Point) -> impl Iterator<Item = (Point, &'a GridCell<T>)> + '_ {
+        NEIGHBOURS8.into_iter().map(move |d| {
+            let p = shift(center, d);
+            (p, self.get_cell(p))
+        })
+    }
+
+    pub fn neighbours(&self, center: Point, connectivity: Connectivity) -> Vec<(Point, &'a GridCell<T>)> {
+        match connectivity {
+            Connectivity::Four => self.neighbours4(center).collect(),
+            Connectivity::Eight => self.neighbours8(center).collect(),
+        }
+    }
+
+    /// Breadth-first search from `start` over the endless plane, going no
+    /// further than `limit` steps. Paths are keyed by unwrapped coordinates,
+    /// so the same cell in two copies is reached separately.
+    pub fn bfs<F>(&self, start: Point, limit: u64, passable: F) -> Paths<Point>
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> bool
+    {
+        let passable = &passable;
+        bfs_by([start], limit, |at| {
+            let from = self.get_cell(at);
+            self.neighbours4(at).filter(move |(_, to)| passable(from, to)).map(|(next, _)| next)
+        })
+    }
+
+    /// Cheapest paths from `start` to everything costing at most `limit`.
+    /// `cost` returns `None` for moves that are not allowed; every allowed
+    /// move should cost at least 1, or the search may never end.
+    pub fn dijkstra<F>(&self, start: Point, limit: u64, cost: F) -> Paths<Point>
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> Option<u64>
+    {
+        let cost = &cost;
+        dijkstra_by([start], limit, |at| {
+            let from = self.get_cell(at);
+            self.neighbours4(at).filter_map(move |(next, to)| Some((next, cost(from, to)?)))
+        })
+    }
+
+    /// Cheapest path from `start` to `goal` and its cost, or `None` if it
+    /// would cost more than `limit`. As with `TextGrid::astar`, `heuristic`
+    /// must never overestimate the remaining cost.
+    pub fn astar<F, H>(&self, start: Point, goal: Point, limit: u64, cost: F, heuristic: H) -> Option<(u64, Vec<Point>)>
+        where F: Fn(&GridCell<T>, &GridCell<T>) -> Option<u64>, H: Fn(Point) -> u64
+    {
+        let cost = &cost;
+        astar_by(start, goal, limit, |at| {
+            let from = self.get_cell(at);
+            self.neighbours4(at).filter_map(move |(next, to)| Some((next, cost(from, to)?)))
+        }, heuristic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libaoc::to_lines;
+
+    fn grid(s: &str) -> TextGrid<u64> {
+        TextGrid::from_lines(&to_lines(s))
+    }
+
+    fn open(_: &GridCell<u64>, to: &GridCell<u64>) -> bool {
+        to.value != '#'
+    }
+
+    fn digit_cost(_: &GridCell<u64>, to: &GridCell<u64>) -> Option<u64> {
+        to.value.to_digit(10).map(u64::from)
+    }
+
+    const GARDEN: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn coordinates_wrap_in_both_directions() {
+        let g = grid("ab\ncd\nef");
+        let w = g.wrapping();
+        assert_eq!(w.get_cell((-1, 0)).value, 'e');
+        assert_eq!(w.get_cell((3, 5)).value, 'b');
+        assert_eq!(w.get_cell((-4, -3)).value, 'f');
+        assert_eq!((w.wrap((-4, -3)), w.tile_of((-4, -3))), (Coord::new(2, 1), (-2, -2)));
+        let around = w.neighbours((0, 0), Connectivity::Four).into_iter().map(|(p, c)| (p, c.value)).collect::<Vec<_>>();
+        assert_eq!(around, vec![((-1, 0), 'e'), ((0, 1), 'b'), ((1, 0), 'c'), ((0, -1), 'b')]);
+        assert_eq!(w.neighbours8((5, 5)).count(), 8);
+    }
+
+    #[test]
+    fn entities_repeat_in_every_copy() {
+        let mut g = grid("....\n....");
+        g.add_entity(Coord::new(1, 1), Coord::new(1, 2), Rc::new(9)).unwrap();
+        let w = g.wrapping();
+        assert_eq!(w.get_entity((-1, -2)), Some(Rc::new(9)));
+        assert_eq!(w.get_entity((7, 13)), Some(Rc::new(9)));
+        assert_eq!(w.get_entity((7, 12)), None);
+    }
+
+    #[test]
+    fn infinite_garden_reach() {
+        let g = grid(GARDEN);
+        let start = g.find_char(|c| c == 'S')[0].start;
+        let reach = g.wrapping().bfs((start.line as i64, start.offset as i64), 50, open);
+        // A plot can be reached in exactly n steps if it is within n and
+        // the same parity away, since the walk can step back and forth.
+        let count = |steps: u64| reach.distances.values().filter(|d| **d <= steps && **d % 2 == steps % 2).count();
+        assert_eq!(count(6), 16);
+        assert_eq!(count(10), 50);
+        assert_eq!(count(50), 1594);
+    }
+
+    #[test]
+    fn weighted_searches_cross_into_other_copies() {
+        let g = grid(GARDEN);
+        let start = g.find_char(|c| c == 'S')[0].start;
+        let start = (start.line as i64, start.offset as i64);
+        let w = g.wrapping();
+        let unit = |from: &GridCell<u64>, to: &GridCell<u64>| open(from, to).then_some(1);
+        let reach = w.bfs(start, 50, open);
+        assert_eq!(w.dijkstra(start, 50, unit).distances, reach.distances);
+        let far = (start.0 - 30, start.1 + 17);
+        let path = reach.path_to(far).unwrap();
+        assert_eq!((path.first(), path.len() as u64 - 1), (Some(&start), reach.distance(far).unwrap()));
+        assert!(path.windows(2).all(|p| p[0].0.abs_diff(p[1].0) + p[0].1.abs_diff(p[1].1) == 1));
+        assert!(path.iter().all(|p| w.get_cell(*p).value != '#'));
+
+        let g = grid("1163751\n1381373\n2136511\n3694931\n7463417\n1319128\n1359912");
+        let w = g.wrapping();
+        let goal = (-1, -1);
+        let manhattan = |p: Point| p.0.abs_diff(goal.0) + p.1.abs_diff(goal.1);
+        let (cost, path) = w.astar((0, 0), goal, 100, digit_cost, manhattan).unwrap();
+        assert_eq!(cost, 3);
+        assert_eq!((path.len(), path.last()), (3, Some(&goal)));
+        let paths = w.dijkstra((0, 0), 100, digit_cost);
+        assert_eq!(paths.distance(goal), Some(cost));
+        assert_eq!(paths.path_to(goal).map(|p| p.len()), Some(3));
+        assert_eq!(w.astar((0, 0), goal, 2, digit_cost, manhattan), None);
+        assert!(w.dijkstra((0, 0), 2, digit_cost).distances.values().all(|d| *d <= 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged_grids_cannot_wrap() {
+        grid("ab\nc").wrapping();
+    }
+}
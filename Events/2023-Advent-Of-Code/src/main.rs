@@ -1,3 +1,241 @@
+use std::{collections::HashMap, env, process};
+
+use advent_of_code_2023::answers::{self, InputKind};
+use advent_of_code_2023::days;
+use advent_of_code_2023::libaoc::{Part, SolveError, Solution};
+use advent_of_code_2023::libaoc::input::read_day_input;
+use advent_of_code_2023::libaoc::provider::{default_provider, InputProvider};
+use advent_of_code_2023::libaoc::render::Style;
+
+const USAGE: &str = "Usage: advent-of-code-2023 --day <N> [--part 1|2] [--input example|input|<path>|-] [--render plain|ansi]
+       advent-of-code-2023 --verify [--day <N>] [--part 1|2] [--input example|input]";
+
+#[derive(Debug, PartialEq)]
+enum InputSource {
+    Example,
+    Input,
+    Path(String)
+}
+
+#[derive(Debug)]
+struct Args {
+    day: Option<u32>,
+    parts: Vec<Part>,
+    input: Option<InputSource>,
+    verify: bool,
+    render: Option<Style>
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("Unknown part '{}', expected 1 or 2", s))
+    }
+}
+
+fn parse_style(s: &str) -> Result<Style, String> {
+    match s {
+        "plain" => Ok(Style::Plain),
+        "ansi" => Ok(Style::Ansi),
+        _ => Err(format!("Unknown style '{}', expected plain or ansi", s))
+    }
+}
+
+fn parse_input_source(s: &str) -> InputSource {
+    match s {
+        "example" => InputSource::Example,
+        "input" => InputSource::Input,
+        path => InputSource::Path(String::from(path))
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut day = None;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input = None;
+    let mut verify = false;
+    let mut render = None;
+
+    let mut it = args.iter();
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().ok_or(format!("Missing value for {}", flag));
+        match flag.as_str() {
+            "--day" => {
+                let v = value()?;
+                day = Some(v.parse::<u32>().map_err(|_| format!("Invalid day '{}'", v))?);
+            },
+            "--part" => parts = vec![parse_part(value()?)?],
+            "--input" => input = Some(parse_input_source(value()?)),
+            "--verify" => verify = true,
+            "--render" => render = Some(parse_style(value()?)?),
+            _ => return Err(format!("Unknown argument '{}'", flag))
+        }
+    }
+
+    if !verify && day.is_none() {
+        return Err(String::from("Missing --day"));
+    }
+    if verify && matches!(input, Some(InputSource::Path(_))) {
+        return Err(String::from("--verify only accepts --input example or --input input"));
+    }
+
+    if verify && render.is_some() {
+        return Err(String::from("--render cannot be combined with --verify"));
+    }
+
+    Ok(Args { day, parts, input, verify, render })
+}
+
+fn input_path(solution: &dyn Solution, part: Part, source: &InputSource) -> String {
+    match source {
+        InputSource::Example => InputKind::Example.path(solution, part),
+        InputSource::Input => InputKind::Input.path(solution, part),
+        InputSource::Path(p) => p.clone()
+    }
+}
+
+fn run(args: &Args) {
+    let day = args.day.unwrap();
+    let solution = days::get(day).unwrap_or_else(|| {
+        eprintln!("No solution registered for day {}", day);
+        process::exit(2);
+    });
+    let source = args.input.as_ref().unwrap_or(&InputSource::Input);
+
+    println!("# Day {:02}", solution.day());
+    let mut failed = false;
+    // Both parts may share a file, and standard input can only be read once.
+    let mut inputs: HashMap<String, String> = HashMap::new();
+    for part in &args.parts {
+        let path = input_path(solution.as_ref(), *part, source);
+        if !inputs.contains_key(&path) {
+            // The real input is downloaded the first time a day is run.
+            let loaded = match source {
+                InputSource::Input => default_provider().input(day).map_err(|e| e.to_string()),
+                _ => read_day_input(day, &path).map_err(|e| e.to_string()),
+            };
+            let loaded = loaded.unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+            inputs.insert(path.clone(), loaded);
+        }
+        let input = &inputs[&path];
+        if let Some(style) = args.render {
+            // The picture is the same for both parts, so draw it once.
+            match solution.render(input, style) {
+                Ok(picture) => print!("{}", picture),
+                Err(SolveError::NotImplemented) => println!("Nothing to render for day {}", day),
+                Err(e) => {
+                    eprintln!("Cannot render {}: {}", path, e);
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+        println!("## Part {}", part);
+        match solution.solve(*part, input) {
+            Ok(answer) => println!("{} ({})", answer, path),
+            Err(SolveError::NotImplemented) => println!("Not implemented yet"),
+            Err(e) => {
+                eprintln!("Part {} failed on {}: {}", part, path, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn verify(args: &Args) {
+    let kind = match args.input {
+        Some(InputSource::Example) => Some(InputKind::Example),
+        Some(InputSource::Input) => Some(InputKind::Input),
+        _ => None,
+    };
+    let solutions = days::all().into_iter()
+        .filter(|s| args.day.is_none_or(|d| s.day() == d));
+
+    let mut checks = vec![];
+    for solution in solutions {
+        let wanted = answers::ANSWERS.iter()
+            .filter(|e| e.day == solution.day())
+            .filter(|e| args.parts.contains(&e.part))
+            .filter(|e| kind.is_none_or(|k| e.input == k));
+        for expected in wanted {
+            checks.push(answers::check(solution.as_ref(), expected));
+        }
+    }
+
+    print!("{}", answers::table(&checks));
+    let failures = checks.iter().filter(|c| !c.passed()).count();
+    println!("{} checked, {} failed", checks.len(), failures);
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+    let args = parse_args(&raw_args).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        process::exit(2);
+    });
+
+    if args.verify {
+        verify(&args);
+    } else {
+        run(&args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_day_part_and_input() {
+        let parsed = parse_args(&args("--day 5 --part 2 --input example")).unwrap();
+        assert_eq!(parsed.day, Some(5));
+        assert_eq!(parsed.parts, vec![Part::Two]);
+        assert_eq!(parsed.input, Some(InputSource::Example));
+    }
+
+    #[test]
+    fn defaults_to_both_parts_of_the_real_input() {
+        let parsed = parse_args(&args("--day 1")).unwrap();
+        assert_eq!(parsed.parts, vec![Part::One, Part::Two]);
+        assert_eq!(parsed.input, None);
+        assert!(!parsed.verify);
+    }
+
+    #[test]
+    fn other_inputs_are_paths() {
+        let parsed = parse_args(&args("--day 8 --input files/08-example-2.txt")).unwrap();
+        assert_eq!(parsed.input, Some(InputSource::Path(String::from("files/08-example-2.txt"))));
+    }
+
+    #[test]
+    fn render_takes_a_style() {
+        assert_eq!(parse_args(&args("--day 10 --render ansi")).unwrap().render, Some(Style::Ansi));
+        assert!(parse_args(&args("--day 10 --render fancy")).is_err());
+        assert!(parse_args(&args("--verify --render plain")).is_err());
+    }
+
+    #[test]
+    fn day_is_required() {
+        assert!(parse_args(&args("--part 1")).is_err());
+    }
+
+    #[test]
+    fn verify_runs_without_a_day_but_not_on_a_path() {
+        assert!(parse_args(&args("--verify")).unwrap().verify);
+        assert!(parse_args(&args("--verify --input files/01-test.txt")).is_err());
+    }
 }